@@ -0,0 +1,325 @@
+//! Minimal Rust tokenizer, keeping track of the byte offsets of each token so
+//! that the source can be rewritten in place (comments and formatting
+//! included).
+
+use ::core::ops::Range;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate)
+enum Kind {
+    Ident,
+    Lifetime,
+    Literal,
+    /// A single punctuation character, or one of `::`, `->`, `=>`.
+    Punct,
+}
+
+#[derive(Debug)]
+pub(crate)
+struct Token {
+    pub(crate) kind: Kind,
+    pub(crate) span: Range<usize>,
+}
+
+#[derive(Debug)]
+pub(crate)
+struct Group {
+    /// `(`, `[`, or `{`.
+    pub(crate) delimiter: u8,
+    /// Delimiters included.
+    pub(crate) span: Range<usize>,
+    /// Delimiters excluded.
+    pub(crate) inner: Range<usize>,
+    pub(crate) trees: Vec<Tree>,
+}
+
+#[derive(Debug)]
+pub(crate)
+enum Tree {
+    Token(Token),
+    Group(Group),
+}
+
+impl Tree {
+    pub(crate)
+    fn span (&self)
+      -> Range<usize>
+    {
+        match self {
+            | Self::Token(token) => token.span.clone(),
+            | Self::Group(group) => group.span.clone(),
+        }
+    }
+}
+
+/// A tokenization error, at the given byte offset.
+pub(crate)
+struct Error {
+    pub(crate) offset: usize,
+    pub(crate) message: &'static str,
+}
+
+/// Tokenizes `src` into token trees.
+pub(crate)
+fn parse (src: &str)
+  -> Result<Vec<Tree>, Error>
+{
+    let mut lexer = Lexer { src: src.as_bytes(), pos: 0 };
+    let mut stack: Vec<(u8, usize, Vec<Tree>)> = vec![];
+    let mut trees = vec![];
+    while let Some((kind, span)) = lexer.next_token()? {
+        let byte = src.as_bytes()[span.start];
+        match (kind, byte) {
+            | (Kind::Punct, b'(' | b'[' | b'{') => {
+                stack.push((byte, span.start, ::core::mem::take(&mut trees)));
+            },
+            | (Kind::Punct, b')' | b']' | b'}') => {
+                let Some((delimiter, start, parent)) = stack.pop() else {
+                    return Err(Error { offset: span.start, message: "unbalanced delimiter" });
+                };
+                if closing(delimiter) != byte {
+                    return Err(Error { offset: span.start, message: "mismatched delimiter" });
+                }
+                let group = Group {
+                    delimiter,
+                    span: start .. span.end,
+                    inner: start + 1 .. span.start,
+                    trees: ::core::mem::replace(&mut trees, parent),
+                };
+                trees.push(Tree::Group(group));
+            },
+            | _ => trees.push(Tree::Token(Token { kind, span })),
+        }
+    }
+    if let Some(&(_, start, _)) = stack.last() {
+        return Err(Error { offset: start, message: "unclosed delimiter" });
+    }
+    Ok(trees)
+}
+
+pub(crate)
+fn closing (delimiter: u8)
+  -> u8
+{
+    match delimiter {
+        | b'(' => b')',
+        | b'[' => b']',
+        | _ => b'}',
+    }
+}
+
+struct Lexer<'src> {
+    src: &'src [u8],
+    pos: usize,
+}
+
+impl Lexer<'_> {
+    fn peek (&self, ahead: usize)
+      -> u8
+    {
+        self.src.get(self.pos + ahead).copied().unwrap_or(0)
+    }
+
+    fn next_token (&mut self)
+      -> Result<Option<(Kind, Range<usize>)>, Error>
+    {
+        self.skip_trivia()?;
+        let start = self.pos;
+        let kind = match self.peek(0) {
+            | 0 if self.pos >= self.src.len() => return Ok(None),
+            | b'"' => {
+                self.string()?;
+                Kind::Literal
+            },
+            | b'\'' => self.quote()?,
+            | b'0' ..= b'9' => {
+                self.number();
+                Kind::Literal
+            },
+            | b'r' if self.peek(1) == b'#' && is_ident_start(self.peek(2)) => {
+                self.pos += 2;
+                self.ident();
+                Kind::Ident
+            },
+            | c if is_ident_start(c) => {
+                self.ident();
+                match (&self.src[start .. self.pos], self.peek(0)) {
+                    | (b"b" | b"c", b'"') => {
+                        self.string()?;
+                        Kind::Literal
+                    },
+                    | (b"b", b'\'') => {
+                        self.quote()?;
+                        Kind::Literal
+                    },
+                    | (b"r" | b"br" | b"cr", b'"' | b'#') => {
+                        self.raw_string()?;
+                        Kind::Literal
+                    },
+                    | _ => Kind::Ident,
+                }
+            },
+            | _ => {
+                let two = [self.peek(0), self.peek(1)];
+                self.pos += if matches!(&two, b"::" | b"->" | b"=>") { 2 } else { 1 };
+                Kind::Punct
+            },
+        };
+        Ok(Some((kind, start .. self.pos)))
+    }
+
+    fn skip_trivia (&mut self)
+      -> Result<(), Error>
+    {
+        loop {
+            match (self.peek(0), self.peek(1)) {
+                | (b'/', b'/') => {
+                    while self.pos < self.src.len() && self.peek(0) != b'\n' {
+                        self.pos += 1;
+                    }
+                },
+                | (b'/', b'*') => {
+                    let start = self.pos;
+                    let mut depth = 0_usize;
+                    loop {
+                        match (self.peek(0), self.peek(1)) {
+                            | (b'/', b'*') => {
+                                depth += 1;
+                                self.pos += 2;
+                            },
+                            | (b'*', b'/') => {
+                                depth -= 1;
+                                self.pos += 2;
+                                if depth == 0 {
+                                    break;
+                                }
+                            },
+                            | _ if self.pos >= self.src.len() => {
+                                return Err(Error { offset: start, message: "unterminated comment" });
+                            },
+                            | _ => self.pos += 1,
+                        }
+                    }
+                },
+                | (c, _) if c.is_ascii_whitespace() => self.pos += 1,
+                | _ => return Ok(()),
+            }
+        }
+    }
+
+    fn ident (&mut self)
+    {
+        while is_ident_continue(self.peek(0)) {
+            self.pos += 1;
+        }
+    }
+
+    fn number (&mut self)
+    {
+        self.ident();
+        if self.peek(0) == b'.' && self.peek(1).is_ascii_digit() {
+            self.pos += 1;
+            self.ident();
+        }
+        if matches!(self.src[self.pos - 1], b'e' | b'E')
+        && matches!(self.peek(0), b'+' | b'-')
+        && self.peek(1).is_ascii_digit()
+        {
+            self.pos += 1;
+            self.ident();
+        }
+    }
+
+    /// At a `"`.
+    fn string (&mut self)
+      -> Result<(), Error>
+    {
+        let start = self.pos;
+        self.pos += 1;
+        loop {
+            match self.peek(0) {
+                | b'"' => break,
+                | b'\\' => self.pos += 2,
+                | _ if self.pos >= self.src.len() => {
+                    return Err(Error { offset: start, message: "unterminated string literal" });
+                },
+                | _ => self.pos += 1,
+            }
+        }
+        self.pos += 1;
+        self.ident(); // suffix
+        Ok(())
+    }
+
+    /// At the `#`s or `"` following an `r`, `br`, or `cr` prefix.
+    fn raw_string (&mut self)
+      -> Result<(), Error>
+    {
+        let start = self.pos;
+        let mut hashes = 0;
+        while self.peek(0) == b'#' {
+            hashes += 1;
+            self.pos += 1;
+        }
+        if self.peek(0) != b'"' {
+            return Err(Error { offset: start, message: "invalid raw string literal" });
+        }
+        self.pos += 1;
+        loop {
+            if self.pos >= self.src.len() {
+                return Err(Error { offset: start, message: "unterminated raw string literal" });
+            }
+            if self.peek(0) == b'"'
+            && (1 ..= hashes).all(|i| self.peek(i) == b'#')
+            {
+                self.pos += 1 + hashes;
+                break;
+            }
+            self.pos += 1;
+        }
+        self.ident(); // suffix
+        Ok(())
+    }
+
+    /// At a `'`: either a character literal, or a lifetime (or label).
+    fn quote (&mut self)
+      -> Result<Kind, Error>
+    {
+        let start = self.pos;
+        self.pos += 1;
+        if self.peek(0) == b'\\' {
+            self.pos += 2;
+            while self.peek(0) != b'\'' {
+                if self.pos >= self.src.len() {
+                    return Err(Error { offset: start, message: "unterminated character literal" });
+                }
+                self.pos += 1;
+            }
+            self.pos += 1;
+            return Ok(Kind::Literal);
+        }
+        // Skip one (possibly multi-byte) character.
+        self.pos += 1;
+        while self.peek(0) & 0b1100_0000 == 0b1000_0000 {
+            self.pos += 1;
+        }
+        if self.peek(0) == b'\'' {
+            self.pos += 1;
+            return Ok(Kind::Literal);
+        }
+        self.ident();
+        Ok(Kind::Lifetime)
+    }
+}
+
+fn is_ident_start (c: u8)
+  -> bool
+{
+    c == b'_' || c.is_ascii_alphabetic() || c >= 0x80
+}
+
+fn is_ident_continue (c: u8)
+  -> bool
+{
+    is_ident_start(c) || c.is_ascii_digit()
+}
@@ -0,0 +1,200 @@
+//! Rewrites the `polonius_the_crab` macro invocations of some Rust sources into
+//! the equivalent plain code, which a borrow checker with native Polonius
+//! support (such as `-Zpolonius`) accepts as is.
+//!
+//! This is the very shape which the `polonius` Cargo feature of
+//! `polonius_the_crab` expands to, minus the closures, `ForLt!` types, and
+//! scaffolding:
+//!
+//! | Invocation | Rewritten into |
+//! |---|---|
+//! | `polonius!(\|var\| -> Ret { body })` | `{ body }` |
+//! | `polonius!('label, \|var\| -> Ret { body })` | `{ body }` |
+//! | `polonius_loop!(\|var\| -> Ret { body })` | `loop { body }` |
+//! | `polonius_return!(e)` | `return e`, or `break 'label e` in a labeled `polonius!` |
+//! | `exit_polonius!(e)` | `break 'exit_polonius e`, labeling the `polonius!` block |
+//! | `polonius_break!(e)` | `break e` |
+//! | `polonius_break_dependent!(e)` | `break e` |
+//! | `polonius_continue!()` | `continue` |
+//! | `polonius_try!(e)` | `e?` |
+//! | `polonius_first_of!(input => […] else \|var\| { … })` | an `if let Some(hit) = … else if let …` chain |
+//!
+//! The `dyn` flavors are rewritten in the same fashion. When a
+//! `polonius_break!` or `polonius_continue!` is nested within another loop,
+//! the `polonius_loop!` gets a `'polonius_loop` label.
+//!
+//! The sites which cannot be translated are reported, and left as they are: a
+//! macro used outside of the body of the macro it belongs to, a
+//! `polonius_try!` within a labeled `polonius!` (its `?` would `return`,
+//! rather than `break` out of the block), or an invocation which could not be
+//! parsed (_e.g._, within a `macro_rules!` definition).
+//!
+//! Some leftovers are cleaned up as well:
+//!
+//!   - the `use` of the `polonius_the_crab` prelude, once nothing needs it;
+//!
+//!   - an `unreachable!();` following a `polonius_loop!` which never
+//!     `break`s, since the resulting `loop` diverges.
+//!
+//! The other leftovers are [noted][Migration::notes] instead: the `mut` which
+//! the macros require on their input bindings (which only the compiler can
+//! tell whether it is still needed), a `use` of the prelude in some other
+//! shape, or any other code following a diverging `loop`.
+//!
+//! ## Example
+//!
+/*!  - ```rust
+    let migration = ::polonius_migrate::migrate(r#"
+        polonius!(|map| -> &'polonius String {
+            if let Some(v) = map.get(&key) {
+                polonius_return!(v);
+            }
+        });
+    "#);
+    assert_eq!(migration.output, r#"
+        {
+            if let Some(v) = map.get(&key) {
+                return v;
+            }
+        };
+    "#);
+    assert!(migration.unsupported.is_empty());
+    assert_eq!(migration.notes.len(), 1); // the `mut`-ness of `map`.
+    ``` */
+#![forbid(unsafe_code)]
+
+mod lexer;
+
+mod rewrite;
+
+/// The outcome of [`migrate()`].
+#[derive(Debug)]
+pub
+struct Migration {
+    /// The rewritten source.
+    pub output: String,
+
+    /// The sites which were left untouched, in order.
+    pub unsupported: Vec<Unsupported>,
+
+    /// The leftovers of the rewrite deserving a manual look, in order.
+    pub notes: Vec<Note>,
+}
+
+/// A site which could not be translated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub
+struct Unsupported {
+    /// Byte offset of the site within the source.
+    pub offset: usize,
+
+    /// Why it could not be translated.
+    pub message: String,
+}
+
+impl Unsupported {
+    /// The 1-based line and column of this site within `src`.
+    pub
+    fn line_column (&self, src: &str)
+      -> (usize, usize)
+    {
+        line_column(src, self.offset)
+    }
+}
+
+/// A leftover of the rewrite, such as a `mut` binding which may no longer be
+/// needed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub
+struct Note {
+    /// Byte offset of the leftover within the source.
+    pub offset: usize,
+
+    /// What to look at.
+    pub message: String,
+}
+
+impl Note {
+    /// The 1-based line and column of this leftover within `src`.
+    pub
+    fn line_column (&self, src: &str)
+      -> (usize, usize)
+    {
+        line_column(src, self.offset)
+    }
+}
+
+fn line_column (src: &str, offset: usize)
+  -> (usize, usize)
+{
+    let before = &src[.. offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |it| it + 1);
+    (line, before[line_start ..].chars().count() + 1)
+}
+
+/// Rewrites the macro invocations of `src`, see the [crate docs][crate].
+///
+/// Should `src` fail to tokenize (_e.g._, unbalanced delimiters), it is left
+/// unchanged, with a single [`Unsupported`] entry.
+pub
+fn migrate (src: &str)
+  -> Migration
+{
+    match lexer::parse(src) {
+        | Ok(trees) => {
+            let mut rewriter = rewrite::Rewriter::new(src);
+            let output = rewriter.file(&trees);
+            let mut migration = Migration {
+                output,
+                unsupported: rewriter.unsupported,
+                notes: rewriter.notes,
+            };
+            if migration.output != src {
+                drop_prelude_import(src, &mut migration);
+            }
+            migration.notes.sort_by_key(|note| note.offset);
+            migration
+        },
+        | Err(error) => Migration {
+            output: src.to_owned(),
+            unsupported: vec![Unsupported {
+                offset: error.offset,
+                message: format!("could not tokenize this file: {}", error.message),
+            }],
+            notes: vec![],
+        },
+    }
+}
+
+/// Drops the lines consisting of a `use` of the prelude (or of such an item
+/// within a `use { … }` group), unless some site was left untouched, or the
+/// `PoloniusExt` method may be in use.
+///
+/// Any other `use` of the prelude is noted.
+fn drop_prelude_import (src: &str, migration: &mut Migration)
+{
+    const PRELUDE: &str = "polonius_the_crab::prelude::*";
+    if !migration.unsupported.is_empty() || migration.output.contains("polonius_get") {
+        return;
+    }
+    let mut output = String::with_capacity(migration.output.len());
+    for line in migration.output.split_inclusive('\n') {
+        let item = line.trim();
+        let item = item.strip_prefix("use ").unwrap_or(item).trim_start();
+        let item = item.strip_prefix("::").unwrap_or(item);
+        if let Some(rest) = item.strip_prefix(PRELUDE) {
+            if matches!(rest, "" | "," | ";") {
+                continue;
+            }
+        }
+        if line.contains("polonius_the_crab::prelude") {
+            migration.notes.push(Note {
+                offset: src.find(line).unwrap_or(0),
+                message: "this `use` of the `polonius_the_crab` prelude may no longer be needed".into(),
+            });
+        }
+        output += line;
+    }
+    migration.output = output;
+}
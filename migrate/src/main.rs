@@ -0,0 +1,116 @@
+//! `polonius-migrate [--check] <PATH>...`
+//!
+//! Rewrites, in place, the `polonius_the_crab` macro invocations of the given
+//! `.rs` files (directories being walked recursively, except for `target/` and
+//! hidden ones), and reports the sites it cannot translate, as well as the
+//! leftovers deserving a manual look (as `note:`s).
+//!
+//! With `--check`, nothing is written: the files which would be rewritten are
+//! listed instead.
+//!
+//! Exits with `1` if some site could not be translated (or, with `--check`,
+//! if some file would be rewritten).
+#![forbid(unsafe_code)]
+
+use ::std::{
+    env,
+    fs,
+    io,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+const USAGE: &str = "usage: polonius-migrate [--check] <PATH>...";
+
+fn main ()
+  -> ExitCode
+{
+    let mut check = false;
+    let mut paths = vec![];
+    for arg in env::args_os().skip(1) {
+        match arg.to_str() {
+            | Some("--check") => check = true,
+            | Some("-h" | "--help") => {
+                println!("{USAGE}");
+                return ExitCode::SUCCESS;
+            },
+            | Some(flag) if flag.starts_with('-') => {
+                eprintln!("unknown flag `{flag}`\n{USAGE}");
+                return ExitCode::from(2);
+            },
+            | _ => paths.push(PathBuf::from(arg)),
+        }
+    }
+    if paths.is_empty() {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    }
+    let mut files = vec![];
+    for path in &paths {
+        if let Err(err) = collect_rs_files(path, &mut files) {
+            eprintln!("{}: {err}", path.display());
+            return ExitCode::from(2);
+        }
+    }
+    let mut failed = false;
+    for file in &files {
+        match migrate_file(file, check) {
+            | Ok(ok) => failed |= !ok,
+            | Err(err) => {
+                eprintln!("{}: {err}", file.display());
+                failed = true;
+            },
+        }
+    }
+    if failed { ExitCode::FAILURE } else { ExitCode::SUCCESS }
+}
+
+/// Whether everything went fine.
+fn migrate_file (file: &Path, check: bool)
+  -> io::Result<bool>
+{
+    let src = fs::read_to_string(file)?;
+    let migration = ::polonius_migrate::migrate(&src);
+    for unsupported in &migration.unsupported {
+        let (line, column) = unsupported.line_column(&src);
+        eprintln!("{}:{line}:{column}: {}", file.display(), unsupported.message);
+    }
+    for note in &migration.notes {
+        let (line, column) = note.line_column(&src);
+        eprintln!("{}:{line}:{column}: note: {}", file.display(), note.message);
+    }
+    let changed = migration.output != src;
+    if changed {
+        if check {
+            println!("would rewrite {}", file.display());
+        } else {
+            fs::write(file, &migration.output)?;
+            println!("rewrote {}", file.display());
+        }
+    }
+    Ok(migration.unsupported.is_empty() && !(check && changed))
+}
+
+fn collect_rs_files (path: &Path, files: &mut Vec<PathBuf>)
+  -> io::Result<()>
+{
+    if !path.is_dir() {
+        files.push(path.to_owned());
+        return Ok(());
+    }
+    let mut entries = fs::read_dir(path)?.collect::<io::Result<Vec<_>>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+    for entry in entries {
+        let path = entry.path();
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if path.is_dir() {
+            if name != "target" && !name.starts_with('.') {
+                collect_rs_files(&path, files)?;
+            }
+        } else if name.ends_with(".rs") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,700 @@
+//! The translation of each macro invocation into plain code.
+
+use {
+    ::core::{
+        cell::Cell,
+        ops::Range,
+    },
+    crate::{
+        lexer::{Group, Kind, Tree},
+        Note,
+        Unsupported,
+    },
+};
+
+const MACROS: &[&str] = &[
+    "exit_polonius",
+    "polonius",
+    "polonius_break",
+    "polonius_break_dependent",
+    "polonius_continue",
+    "polonius_first_of",
+    "polonius_loop",
+    "polonius_return",
+    "polonius_try",
+];
+
+/// Identifiers which, at the top level of an expression, make it bind less
+/// tightly than a postfix `?`.
+const LOOSE_KEYWORDS: &[&str] = &[
+    "as", "async", "break", "continue", "if", "let", "loop", "match", "move",
+    "return", "unsafe", "while", "yield",
+];
+
+/// Which macro body the tokens being rewritten belong to, which determines
+/// what `polonius_return!` & co. become.
+enum ScopeKind<'src> {
+    /// Not within any of this crate's macros.
+    Outside,
+
+    /// Within a `polonius!` body, with its optional `'label`.
+    Polonius {
+        label: Option<&'src str>,
+    },
+
+    /// Within a `polonius_loop!` body.
+    Loop,
+
+    /// Within a `polonius_first_of!` lookup, _i.e._, within a closure.
+    Lookup,
+}
+
+struct Scope<'src> {
+    kind: ScopeKind<'src>,
+    /// Whether the body needs its generated label: `'exit_polonius` for
+    /// `exit_polonius!`, or `'polonius_loop` for a `polonius_break!` or
+    /// `polonius_continue!` nested within an inner loop.
+    label_used: Cell<bool>,
+    /// Whether a `polonius_loop!` body `break`s out of the loop.
+    breaks: Cell<bool>,
+}
+
+impl<'src> Scope<'src> {
+    fn new (kind: ScopeKind<'src>)
+      -> Self
+    {
+        Self { kind, label_used: Cell::new(false), breaks: Cell::new(false) }
+    }
+
+    /// The bodies of `polonius!` and `polonius_loop!` cannot be nested within
+    /// one another (the inner `return`s would have the wrong type), so these
+    /// labels never clash.
+    fn label (&self)
+      -> &'static str
+    {
+        match self.kind {
+            | ScopeKind::Loop => "'polonius_loop",
+            | _ => "'exit_polonius",
+        }
+    }
+
+    fn use_label (&self)
+      -> &'static str
+    {
+        self.label_used.set(true);
+        self.label()
+    }
+
+    fn wrap (&self, prefix: &str, body: String)
+      -> String
+    {
+        if self.label_used.get() {
+            format!("{}: {prefix}{body}", self.label())
+        } else {
+            format!("{prefix}{body}")
+        }
+    }
+}
+
+struct Invocation<'t> {
+    name: &'static str,
+    /// From the (optional) path to the closing delimiter.
+    span: Range<usize>,
+    args: &'t Group,
+    /// Index of the tree following the invocation.
+    next: usize,
+}
+
+pub(crate)
+struct Rewriter<'src> {
+    src: &'src str,
+    pub(crate) unsupported: Vec<Unsupported>,
+    pub(crate) notes: Vec<Note>,
+    /// The end offsets of the `polonius_loop!` invocations whose body never
+    /// `break`s, _i.e._, whose resulting `loop` diverges.
+    diverging_loops: Vec<usize>,
+}
+
+impl<'src> Rewriter<'src> {
+    pub(crate)
+    fn new (src: &'src str)
+      -> Self
+    {
+        Self { src, unsupported: vec![], notes: vec![], diverging_loops: vec![] }
+    }
+
+    /// Rewrites the whole file.
+    pub(crate)
+    fn file (&mut self, trees: &[Tree])
+      -> String
+    {
+        let outside = Scope::new(ScopeKind::Outside);
+        match self.render(trees, 0 .. self.src.len(), &outside, false) {
+            | Ok(it) => it,
+            | Err(_) => unreachable!("errors are recorded at the top level"),
+        }
+    }
+
+    fn text (&self, span: Range<usize>)
+      -> &'src str
+    {
+        &self.src[span]
+    }
+
+    fn is (&self, tree: Option<&Tree>, kind: Kind, text: &str)
+      -> bool
+    {
+        matches!(tree, Some(Tree::Token(token))
+            if token.kind == kind && self.text(token.span.clone()) == text
+        )
+    }
+
+    /// The source text of `span`, with the macro invocations within `trees`
+    /// rewritten.
+    ///
+    /// `nested_loop` tells whether these tokens are within a `loop`, `while`,
+    /// or `for` body nested inside the innermost macro body.
+    fn render (
+        &mut self,
+        trees: &[Tree],
+        span: Range<usize>,
+        scope: &Scope<'src>,
+        nested_loop: bool,
+    ) -> Result<String, Unsupported>
+    {
+        let mut out = String::new();
+        let mut pos = span.start;
+        let mut loop_header = false;
+        let mut i = 0;
+        while i < trees.len() {
+            if let Some(invocation) = self.invocation_at(trees, i) {
+                let notes = self.notes.len();
+                let translated = match self.translate(&invocation, scope, nested_loop) {
+                    | Ok(it) => it,
+                    | Err(unsupported) => {
+                        if let ScopeKind::Outside = scope.kind {
+                            // Leave the whole site as is.
+                            self.notes.truncate(notes);
+                            self.unsupported.push(unsupported);
+                            self.text(invocation.span.clone()).to_owned()
+                        } else {
+                            return Err(unsupported);
+                        }
+                    },
+                };
+                out += self.text(pos .. invocation.span.start);
+                out += &translated;
+                pos = invocation.span.end;
+                i = invocation.next;
+                if self.diverging_loops.contains(&invocation.span.end)
+                && self.is(trees.get(i), Kind::Punct, ";")
+                {
+                    out += ";";
+                    pos = trees[i].span().end;
+                    i += 1;
+                    self.unreachable_after_loop(trees, &mut pos, &mut i);
+                }
+                continue;
+            }
+            match &trees[i] {
+                | Tree::Group(group) => {
+                    let nested = nested_loop || (loop_header && group.delimiter == b'{');
+                    if group.delimiter == b'{' {
+                        loop_header = false;
+                    }
+                    out += self.text(pos .. group.inner.start);
+                    out += &self.render(&group.trees, group.inner.clone(), scope, nested)?;
+                    pos = group.inner.end;
+                },
+                | Tree::Token(token) => match (token.kind, self.text(token.span.clone())) {
+                    | (Kind::Ident, "loop" | "while") => loop_header = true,
+                    | (Kind::Ident, "for") => {
+                        // Not a `for<'lt>` quantification.
+                        loop_header = !self.is(trees.get(i + 1), Kind::Punct, "<");
+                    },
+                    | (Kind::Punct, ";") => loop_header = false,
+                    | _ => {},
+                },
+            }
+            i += 1;
+        }
+        out += self.text(pos .. span.end);
+        Ok(out)
+    }
+
+    /// Drops an `unreachable!();` following a `loop` which never `break`s (at
+    /// `trees[*i]`), as it would now be flagged as unreachable code; notes any
+    /// other following code.
+    fn unreachable_after_loop (&mut self, trees: &[Tree], pos: &mut usize, i: &mut usize)
+    {
+        if self.is(trees.get(*i), Kind::Ident, "unreachable")
+        && self.is(trees.get(*i + 1), Kind::Punct, "!")
+        && matches!(trees.get(*i + 2), Some(Tree::Group(_)))
+        {
+            *i += 3;
+            if self.is(trees.get(*i), Kind::Punct, ";") {
+                *i += 1;
+            }
+            *pos = trees[*i - 1].span().end;
+        }
+        if let Some(next) = trees.get(*i) {
+            self.notes.push(Note {
+                offset: next.span().start,
+                message: "unreachable code: the preceding `polonius_loop!` never `break`s".into(),
+            });
+        }
+    }
+
+    /// Renders the arguments of the invocation at `at`, dedenting them to the
+    /// level of the invocation should they be on their own lines.
+    fn render_args (
+        &mut self,
+        at: usize,
+        args: &[Tree],
+        scope: &Scope<'src>,
+        nested_loop: bool,
+    ) -> Result<String, Unsupported>
+    {
+        let value = self.render_slice(args, scope, nested_loop)?;
+        let Some(first) = args.first() else { return Ok(value) };
+        let start = first.span().start;
+        if !self.src[at .. start].contains('\n') || self.has_multiline_literal(args) {
+            return Ok(value);
+        }
+        let outer = self.indentation_at(at);
+        let inner = self.indentation_at(start);
+        let Some(extra) = inner.strip_prefix(outer).filter(|it| !it.is_empty()) else {
+            return Ok(value);
+        };
+        Ok(value.lines().map(|line| line.strip_prefix(extra).unwrap_or(line)).collect::<Vec<_>>().join("\n"))
+    }
+
+    /// Renders the given trees, which form an expression.
+    fn render_slice (&mut self, trees: &[Tree], scope: &Scope<'src>, nested_loop: bool)
+      -> Result<String, Unsupported>
+    {
+        match (trees.first(), trees.last()) {
+            | (Some(first), Some(last)) => {
+                let span = first.span().start .. last.span().end;
+                self.render(trees, span, scope, nested_loop)
+            },
+            | _ => Ok(String::new()),
+        }
+    }
+
+    /// Matches `(::)? (path ::)* name ! (…)`, for one of our macro names.
+    fn invocation_at<'t> (&self, trees: &'t [Tree], start: usize)
+      -> Option<Invocation<'t>>
+    {
+        if let Some(previous) = start.checked_sub(1).and_then(|it| trees.get(it)) {
+            let previous = self.text(previous.span());
+            let current = self.text(trees[start].span());
+            // Within a `macro_rules!` definition (`$crate::…`), or within a
+            // path whose start was not one of our macros.
+            if previous == "$" || previous == "::" || current == "::" && is_ident(previous) {
+                return None;
+            }
+        }
+        let mut i = start;
+        if self.is(trees.get(i), Kind::Punct, "::") {
+            i += 1;
+        }
+        loop {
+            let Some(Tree::Token(ident)) = trees.get(i) else { return None };
+            if ident.kind != Kind::Ident {
+                return None;
+            }
+            if self.is(trees.get(i + 1), Kind::Punct, "::") {
+                i += 2;
+                continue;
+            }
+            let name = self.text(ident.span.clone());
+            let &name = MACROS.iter().find(|&&it| it == name)?;
+            if !self.is(trees.get(i + 1), Kind::Punct, "!") {
+                return None;
+            }
+            let Some(Tree::Group(args)) = trees.get(i + 2) else { return None };
+            return Some(Invocation {
+                name,
+                span: trees[start].span().start .. args.span.end,
+                args,
+                next: i + 3,
+            });
+        }
+    }
+
+    fn translate (
+        &mut self,
+        invocation: &Invocation<'_>,
+        scope: &Scope<'src>,
+        nested_loop: bool,
+    ) -> Result<String, Unsupported>
+    {
+        let at = invocation.span.start;
+        let name = invocation.name;
+        let unsupported = |message: String| Unsupported { offset: at, message };
+        let args = strip_trailing_comma(self, &invocation.args.trees);
+        let stray = || unsupported(match scope.kind {
+            | ScopeKind::Lookup => format!(
+                "`{name}!` within a `polonius_first_of!` lookup",
+            ),
+            | _ => format!(
+                "`{name}!` outside of a `polonius!` or `polonius_loop!` body",
+            ),
+        });
+        match name {
+            | "polonius" => self.polonius(invocation),
+            | "polonius_loop" => self.polonius_loop(invocation),
+            | "polonius_first_of" => self.polonius_first_of(invocation, scope, nested_loop),
+            | "polonius_return" => {
+                if args.is_empty() {
+                    return Err(unsupported("`polonius_return!` without a value".into()));
+                }
+                let value = self.render_args(at, args, scope, nested_loop)?;
+                match scope.kind {
+                    | ScopeKind::Polonius { label: Some(label) } => Ok(format!("break {label} {value}")),
+                    | ScopeKind::Polonius { label: None } | ScopeKind::Loop => Ok(format!("return {value}")),
+                    | _ => Err(stray()),
+                }
+            },
+            | "exit_polonius" => {
+                let value = self.render_args(at, args, scope, nested_loop)?;
+                match scope.kind {
+                    | ScopeKind::Polonius { .. } => Ok(with_value(format!("break {}", scope.use_label()), &value)),
+                    | ScopeKind::Loop => Err(unsupported(
+                        "`exit_polonius!` within a `polonius_loop!` body (use `polonius_break!` instead)".into(),
+                    )),
+                    | _ => Err(stray()),
+                }
+            },
+            | "polonius_break" | "polonius_break_dependent" | "polonius_continue" => {
+                if name == "polonius_break_dependent" && args.is_empty() {
+                    return Err(unsupported("`polonius_break_dependent!` without a value".into()));
+                }
+                if name == "polonius_continue" && !args.is_empty() {
+                    return Err(unsupported("`polonius_continue!` with arguments".into()));
+                }
+                let value = self.render_args(at, args, scope, nested_loop)?;
+                let keyword = if name == "polonius_continue" { "continue" } else { "break" };
+                match scope.kind {
+                    | ScopeKind::Loop => {
+                        if keyword == "break" {
+                            scope.breaks.set(true);
+                        }
+                        if nested_loop {
+                            Ok(with_value(format!("{keyword} {}", scope.use_label()), &value))
+                        } else {
+                            Ok(with_value(keyword.to_owned(), &value))
+                        }
+                    },
+                    | ScopeKind::Polonius { .. } => Err(unsupported(format!(
+                        "`{name}!` within a `polonius!` body, which is not a loop",
+                    ))),
+                    | _ => Err(stray()),
+                }
+            },
+            | "polonius_try" => {
+                if args.is_empty() {
+                    return Err(unsupported("`polonius_try!` without an expression".into()));
+                }
+                let value = self.render_args(at, args, scope, nested_loop)?;
+                match scope.kind {
+                    | ScopeKind::Polonius { label: None } | ScopeKind::Loop => {
+                        if self.binds_tighter_than_question_mark(args) {
+                            Ok(format!("{value}?"))
+                        } else {
+                            Ok(format!("({value})?"))
+                        }
+                    },
+                    | ScopeKind::Polonius { label: Some(_) } => Err(unsupported(
+                        "`polonius_try!` within a labeled `polonius!` body: a `?` would return from the function, rather than break out of the block".into(),
+                    )),
+                    | _ => Err(stray()),
+                }
+            },
+            | _ => unreachable!(),
+        }
+    }
+
+    /// `polonius!($('label,)? $(dyn)? |var| -> Ret { body })`
+    fn polonius (&mut self, invocation: &Invocation<'_>)
+      -> Result<String, Unsupported>
+    {
+        let mut args = strip_trailing_comma(self, &invocation.args.trees);
+        let mut label = None;
+        if let [Tree::Token(lifetime), comma, rest @ ..] = args {
+            if lifetime.kind == Kind::Lifetime && self.is(Some(comma), Kind::Punct, ",") {
+                label = Some(self.text(lifetime.span.clone()));
+                args = rest;
+            }
+        }
+        let (var, body) = self.closure_like(args).ok_or_else(|| Unsupported {
+            offset: invocation.span.start,
+            message: "could not parse this `polonius!` invocation".into(),
+        })?;
+        let scope = Scope::new(ScopeKind::Polonius { label });
+        let inner = self.render(&body.trees, body.inner.clone(), &scope, false)?;
+        self.note_mut_binding(invocation, var);
+        Ok(scope.wrap("", format!("{{{inner}}}")))
+    }
+
+    /// `polonius_loop!($(dyn)? |var| -> Ret $(, break: Break)? { body })`
+    fn polonius_loop (&mut self, invocation: &Invocation<'_>)
+      -> Result<String, Unsupported>
+    {
+        let args = strip_trailing_comma(self, &invocation.args.trees);
+        let (var, body) = self.closure_like(args).ok_or_else(|| Unsupported {
+            offset: invocation.span.start,
+            message: "could not parse this `polonius_loop!` invocation".into(),
+        })?;
+        let scope = Scope::new(ScopeKind::Loop);
+        let inner = self.render(&body.trees, body.inner.clone(), &scope, false)?;
+        self.note_mut_binding(invocation, var);
+        if !scope.breaks.get() {
+            self.diverging_loops.push(invocation.span.end);
+        }
+        Ok(scope.wrap("loop ", format!("{{{inner}}}")))
+    }
+
+    /// The macros require their input variable to be a `mut` binding, which
+    /// the plain code may not need anymore (`unused_mut`), but only the
+    /// compiler can tell.
+    fn note_mut_binding (&mut self, invocation: &Invocation<'_>, var: &str)
+    {
+        self.notes.push(Note {
+            offset: invocation.span.start,
+            message: format!(
+                "`{var}` may no longer need to be a `mut` binding (`{}!` required it)",
+                invocation.name,
+            ),
+        });
+    }
+
+    /// Parses `$(dyn)? |var $(,)?| -> … { body }`, returning `var` and the
+    /// body.
+    fn closure_like<'t> (&self, mut args: &'t [Tree])
+      -> Option<(&'src str, &'t Group)>
+    {
+        if self.is(args.first(), Kind::Ident, "dyn") {
+            args = &args[1 ..];
+        }
+        let [bar, Tree::Token(var), rest @ ..] = args else { return None };
+        if !self.is(Some(bar), Kind::Punct, "|") || var.kind != Kind::Ident {
+            return None;
+        }
+        let rest = match rest {
+            | [comma, rest @ ..] if self.is(Some(comma), Kind::Punct, ",") => rest,
+            | _ => rest,
+        };
+        let [bar, arrow, _, .., Tree::Group(body)] = rest else { return None };
+        if !self.is(Some(bar), Kind::Punct, "|")
+        || !self.is(Some(arrow), Kind::Punct, "->")
+        || body.delimiter != b'{'
+        {
+            return None;
+        }
+        Some((self.text(var.span.clone()), body))
+    }
+
+    /// `polonius_first_of!(input => [ $(|binding| lookup),+ ] else |binding| $(-> Ret)? { … })`
+    fn polonius_first_of (
+        &mut self,
+        invocation: &Invocation<'_>,
+        scope: &Scope<'src>,
+        nested_loop: bool,
+    ) -> Result<String, Unsupported>
+    {
+        let (input, lookups, else_binding, else_block) =
+            self.parse_first_of(&invocation.args.trees).ok_or_else(|| Unsupported {
+                offset: invocation.span.start,
+                message: "could not parse this `polonius_first_of!` invocation".into(),
+            })?
+        ;
+        let indent = self.indentation_at(invocation.span.start);
+        let input = self.render_slice(input, scope, nested_loop)?;
+        let mut out = format!("{{\n{indent}    let input_borrow: &mut _ = {input};\n{indent}    ");
+        let lookup_scope = Scope::new(ScopeKind::Lookup);
+        for (binding, lookup) in lookups {
+            let lookup = self.render_slice(lookup, &lookup_scope, false)?;
+            out += &format!(
+                "if let Some(hit) = {{ let {binding} = &mut *input_borrow; {lookup} }} {{\n\
+                {indent}        hit\n\
+                {indent}    }} else ",
+            );
+        }
+        let mut body = self.render(&else_block.trees, else_block.inner.clone(), scope, nested_loop)?;
+        if !self.has_multiline_literal(&else_block.trees) {
+            body = reindent(body.trim(), "    ");
+        }
+        out += &format!(
+            "{{\n\
+            {indent}        let {else_binding} = input_borrow;\n\
+            {indent}        {body}\n\
+            {indent}    }}\n\
+            {indent}}}",
+        );
+        Ok(out)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn parse_first_of<'t> (&self, args: &'t [Tree])
+      -> Option<(&'t [Tree], Vec<(&'src str, &'t [Tree])>, &'src str, &'t Group)>
+    {
+        let args = strip_trailing_comma(self, args);
+        let arrow = args.iter().position(|it| self.is(Some(it), Kind::Punct, "=>"))?;
+        let (input, rest) = args.split_at(arrow);
+        let [_, Tree::Group(list), r#else, rest @ ..] = rest else { return None };
+        if input.is_empty()
+        || list.delimiter != b'['
+        || !self.is(Some(r#else), Kind::Ident, "else")
+        {
+            return None;
+        }
+        let mut lookups = vec![];
+        for lookup in split_commas(self, strip_trailing_comma(self, &list.trees)) {
+            let (binding, expr) = self.binding(lookup)?;
+            if expr.is_empty() {
+                return None;
+            }
+            lookups.push((binding, expr));
+        }
+        if lookups.is_empty() {
+            return None;
+        }
+        let (else_binding, rest) = self.binding(rest)?;
+        let Some(Tree::Group(else_block)) = rest.last() else { return None };
+        if else_block.delimiter != b'{'
+        || !(rest.len() == 1 || self.is(rest.first(), Kind::Punct, "->"))
+        {
+            return None;
+        }
+        Some((input, lookups, else_binding, else_block))
+    }
+
+    /// Parses `|binding|`, returning the trees which follow.
+    fn binding<'t> (&self, trees: &'t [Tree])
+      -> Option<(&'src str, &'t [Tree])>
+    {
+        let [bar, Tree::Token(binding), bar2, rest @ ..] = trees else { return None };
+        if !self.is(Some(bar), Kind::Punct, "|")
+        || binding.kind != Kind::Ident
+        || !self.is(Some(bar2), Kind::Punct, "|")
+        {
+            return None;
+        }
+        Some((self.text(binding.span.clone()), rest))
+    }
+
+    /// Whether `expr?` parses as `(expr)?`.
+    fn binds_tighter_than_question_mark (&self, trees: &[Tree])
+      -> bool
+    {
+        let mut i = 0;
+        while let Some(tree) = trees.get(i) {
+            let Tree::Token(token) = tree else {
+                i += 1;
+                continue;
+            };
+            let text = self.text(token.span.clone());
+            let ok = match token.kind {
+                | Kind::Literal => true,
+                | Kind::Lifetime => false,
+                | Kind::Ident => !LOOSE_KEYWORDS.contains(&text),
+                // Turbofish: skip the generic arguments.
+                | Kind::Punct if text == "::" && self.is(trees.get(i + 1), Kind::Punct, "<") => {
+                    let mut depth = 0_usize;
+                    for tree in &trees[i + 1 ..] {
+                        i += 1;
+                        if self.is(Some(tree), Kind::Punct, "<") {
+                            depth += 1;
+                        } else if self.is(Some(tree), Kind::Punct, ">") {
+                            depth -= 1;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                    }
+                    depth == 0
+                },
+                // Macro invocation.
+                | Kind::Punct if text == "!" => {
+                    i > 0 && matches!(trees.get(i + 1), Some(Tree::Group(_)))
+                },
+                | Kind::Punct => matches!(text, "." | "::" | "?"),
+            };
+            if !ok {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    fn has_multiline_literal (&self, trees: &[Tree])
+      -> bool
+    {
+        trees.iter().any(|tree| match tree {
+            | Tree::Group(group) => self.has_multiline_literal(&group.trees),
+            | Tree::Token(token) => {
+                token.kind == Kind::Literal && self.text(token.span.clone()).contains('\n')
+            },
+        })
+    }
+
+    /// The leading whitespace of the line of `offset`.
+    fn indentation_at (&self, offset: usize)
+      -> &'src str
+    {
+        let line_start = self.src[.. offset].rfind('\n').map_or(0, |it| it + 1);
+        let line = &self.src[line_start ..];
+        &line[.. line.len() - line.trim_start().len()]
+    }
+}
+
+fn is_ident (text: &str)
+  -> bool
+{
+    text.starts_with(|c: char| c == '_' || c.is_alphabetic())
+}
+
+fn with_value (keyword: String, value: &str)
+  -> String
+{
+    if value.is_empty() {
+        keyword
+    } else {
+        format!("{keyword} {value}")
+    }
+}
+
+/// Indents every line but the first one (which follows some generated code)
+/// with an extra `indent`.
+fn reindent (text: &str, indent: &str)
+  -> String
+{
+    let mut lines = text.lines();
+    let mut out = lines.next().unwrap_or_default().to_owned();
+    for line in lines {
+        out += "\n";
+        if !line.is_empty() {
+            out += indent;
+            out += line;
+        }
+    }
+    out
+}
+
+fn strip_trailing_comma<'t> (rewriter: &Rewriter<'_>, trees: &'t [Tree])
+  -> &'t [Tree]
+{
+    match trees {
+        | [rest @ .., comma] if rewriter.is(Some(comma), Kind::Punct, ",") => rest,
+        | _ => trees,
+    }
+}
+
+fn split_commas<'t> (rewriter: &Rewriter<'_>, trees: &'t [Tree])
+  -> Vec<&'t [Tree]>
+{
+    trees.split(|it| rewriter.is(Some(it), Kind::Punct, ",")).collect()
+}
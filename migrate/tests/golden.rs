@@ -0,0 +1,141 @@
+#![forbid(unsafe_code)]
+
+//! Each `tests/golden/<name>.rs` is migrated, and compared against
+//! `<name>.out.rs`, as well as against the reported unsupported sites and
+//! notes in `<name>.report` (absent when there are none).
+//!
+//! Run with `BLESS=1` to (re)generate the expected files.
+
+use ::std::{
+    env,
+    fs,
+    path::Path,
+};
+
+#[test]
+fn golden ()
+{
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden");
+    let bless = env::var_os("BLESS").is_some();
+    let mut inputs = fs::read_dir(&dir).unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            let name = path.file_name().unwrap().to_str().unwrap();
+            name.ends_with(".rs") && !name.ends_with(".out.rs")
+        })
+        .collect::<Vec<_>>()
+    ;
+    inputs.sort();
+    assert!(!inputs.is_empty());
+    let mut mismatches = vec![];
+    for input in &inputs {
+        let src = fs::read_to_string(input).unwrap();
+        let migration = ::polonius_migrate::migrate(&src);
+        let mut report = migration.unsupported.iter().map(|unsupported| {
+            (unsupported.offset, unsupported.line_column(&src), unsupported.message.clone())
+        }).chain(migration.notes.iter().map(|note| {
+            (note.offset, note.line_column(&src), format!("note: {}", note.message))
+        })).collect::<Vec<_>>();
+        report.sort_by_key(|&(offset, ..)| offset);
+        let report = report.into_iter().map(|(_, (line, column), message)| {
+            format!("{line}:{column}: {message}\n")
+        }).collect::<String>();
+        let outputs = [
+            (input.with_extension("out.rs"), Some(migration.output)),
+            (input.with_extension("report"), Some(report).filter(|it| !it.is_empty())),
+        ];
+        for (path, actual) in outputs {
+            let expected = fs::read_to_string(&path).ok();
+            if expected == actual {
+                continue;
+            }
+            if bless {
+                match actual {
+                    | Some(actual) => fs::write(&path, actual).unwrap(),
+                    | None => fs::remove_file(&path).unwrap(),
+                }
+            } else {
+                mismatches.push(format!(
+                    "{}:\n--- expected\n{}\n--- actual\n{}",
+                    path.display(),
+                    expected.as_deref().unwrap_or("<none>"),
+                    actual.as_deref().unwrap_or("<none>"),
+                ));
+            }
+        }
+    }
+    assert!(mismatches.is_empty(), "\n{}\n(run with `BLESS=1` to update)", mismatches.join("\n"));
+}
+
+#[test]
+fn idempotent ()
+{
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden");
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.to_str().unwrap().ends_with(".out.rs") {
+            let src = fs::read_to_string(&path).unwrap();
+            let migration = ::polonius_migrate::migrate(&src);
+            if migration.unsupported.is_empty() {
+                assert_eq!(migration.output, src, "{}", path.display());
+            }
+        }
+    }
+}
+
+#[test]
+fn unbalanced_delimiters_are_left_as_is ()
+{
+    let src = "fn f () { polonius!(|x| -> &'polonius () { x }) ";
+    let migration = ::polonius_migrate::migrate(src);
+    assert_eq!(migration.output, src);
+    assert_eq!(migration.unsupported.len(), 1);
+    assert_eq!(migration.unsupported[0].line_column(src), (1, 9));
+}
+
+/// The outputs are to be accepted by native Polonius: `cargo check` them, as
+/// modules of a scratch crate, with `-Zpolonius`.
+#[test]
+fn outputs_compile_with_polonius ()
+{
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let crate_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("golden-outputs");
+    fs::create_dir_all(crate_dir.join("src")).unwrap();
+    fs::write(crate_dir.join("Cargo.toml"), format!(r#"
+        [package]
+        name = "golden-outputs"
+        version = "0.0.0"
+        edition = "2021"
+
+        [dependencies]
+        polonius-the-crab.path = {:?}
+
+        [workspace]
+    "#, manifest_dir.parent().unwrap())).unwrap();
+    // The noted leftovers are expected.
+    let mut lib = String::from("#![allow(dead_code, unreachable_code, unused_imports, unused_mut)]\n");
+    let mut outputs = fs::read_dir(manifest_dir.join("tests/golden")).unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.to_str().unwrap().ends_with(".out.rs"))
+        .collect::<Vec<_>>()
+    ;
+    outputs.sort();
+    for path in outputs {
+        // The sites left untouched need not compile.
+        if ::polonius_migrate::migrate(&fs::read_to_string(&path).unwrap()).unsupported.is_empty() {
+            let name = path.file_name().unwrap().to_str().unwrap().trim_end_matches(".out.rs");
+            lib += &format!("#[path = {path:?}] mod {name};\n");
+        }
+    }
+    fs::write(crate_dir.join("src/lib.rs"), lib).unwrap();
+    let status =
+        ::std::process::Command::new(env!("CARGO"))
+            .current_dir(&crate_dir)
+            .env("RUSTC_BOOTSTRAP", "1")
+            .env("RUSTFLAGS", "-Zpolonius")
+            .args(["check", "--quiet"])
+            .status()
+            .unwrap()
+    ;
+    assert!(status.success());
+}
@@ -0,0 +1,17 @@
+//! The leftovers which are noted rather than cleaned up.
+
+use ::polonius_the_crab::prelude::{polonius_loop, polonius_return};
+
+fn cycle (mut v: &'_ mut Vec<String>)
+  -> &'_ mut String
+{
+    let mut i = 0;
+    loop {
+        if !v[i].is_empty() {
+            return &mut v[i];
+        }
+        i = (i + 1) % v.len();
+    };
+    v.push(String::new());
+    v.last_mut().unwrap()
+}
@@ -0,0 +1,68 @@
+//! Nested invocations, and nested loops.
+
+use {
+    ::std::collections::HashMap,
+};
+
+fn first_of_within_polonius (mut map: &'_ mut HashMap<u8, String>)
+  -> &'_ mut String
+{
+    {
+        if map.len() > 1 {
+            return {
+                let input_borrow: &mut _ = map;
+                if let Some(hit) = { let m = &mut *input_borrow; m.get_mut(&0) } {
+                    hit
+                } else {
+                    let m = input_borrow;
+                    m.entry(1).or_default()
+                }
+            };
+        }
+    };
+    map.entry(2).or_default()
+}
+
+fn first_of_within_polonius_loop (mut map: &'_ mut HashMap<u8, String>)
+  -> &'_ mut String
+{
+    let mut key = 0;
+    let missing = loop {
+        let found = {
+            let input_borrow: &mut _ = map;
+            if let Some(hit) = { let m = &mut *input_borrow; m.get_mut(&key).filter(|v| !v.is_empty()) } {
+                hit
+            } else {
+                let m = input_borrow;
+                if !m.contains_key(&key) {
+                    break key;
+                }
+                key += 1;
+                continue;
+            }
+        };
+        return found;
+    };
+    map.entry(missing).or_default()
+}
+
+fn exit_from_an_inner_loop (mut lines: &'_ mut Vec<String>, mut n: usize)
+  -> &'_ mut String
+{
+    for _ in 0 .. 2 {
+        n = 'exit_polonius: {
+            while n > 0 {
+                if n < lines.len() {
+                    return &mut lines[n];
+                }
+                if n > 10 {
+                    break 'exit_polonius n / 2;
+                }
+                n -= 1;
+            }
+            n
+        };
+    }
+    lines.push(String::new());
+    lines.last_mut().unwrap()
+}
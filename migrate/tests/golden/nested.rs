@@ -0,0 +1,59 @@
+//! Nested invocations, and nested loops.
+
+use {
+    ::polonius_the_crab::prelude::*,
+    ::std::collections::HashMap,
+};
+
+fn first_of_within_polonius (mut map: &'_ mut HashMap<u8, String>)
+  -> &'_ mut String
+{
+    polonius!(|map| -> &'polonius mut String {
+        if map.len() > 1 {
+            polonius_return!(polonius_first_of!(map => [|m| m.get_mut(&0)] else |m| {
+                m.entry(1).or_default()
+            }));
+        }
+    });
+    map.entry(2).or_default()
+}
+
+fn first_of_within_polonius_loop (mut map: &'_ mut HashMap<u8, String>)
+  -> &'_ mut String
+{
+    let mut key = 0;
+    let missing = polonius_loop!(|map| -> &'polonius mut String {
+        let found = polonius_first_of!(map => [
+            |m| m.get_mut(&key).filter(|v| !v.is_empty()),
+        ] else |m| -> &'polonius mut String {
+            if !m.contains_key(&key) {
+                polonius_break!(key);
+            }
+            key += 1;
+            polonius_continue!();
+        });
+        polonius_return!(found);
+    });
+    map.entry(missing).or_default()
+}
+
+fn exit_from_an_inner_loop (mut lines: &'_ mut Vec<String>, mut n: usize)
+  -> &'_ mut String
+{
+    for _ in 0 .. 2 {
+        n = polonius!(|lines| -> &'polonius mut String {
+            while n > 0 {
+                if n < lines.len() {
+                    polonius_return!(&mut lines[n]);
+                }
+                if n > 10 {
+                    exit_polonius!(n / 2);
+                }
+                n -= 1;
+            }
+            n
+        });
+    }
+    lines.push(String::new());
+    lines.last_mut().unwrap()
+}
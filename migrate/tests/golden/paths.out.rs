@@ -0,0 +1,44 @@
+//! Path-qualified invocations, and other delimiters.
+
+use ::std::collections::HashMap;
+
+fn get_or_insert (mut map: &'_ mut HashMap<i32, String>)
+  -> &'_ String
+{
+    {
+        if let Some(v) = map.get(&22) {
+            return v;
+        }
+    }
+    map.insert(22, String::from("…"));
+    &map[&22]
+}
+
+fn first_non_empty (mut v: &'_ mut Vec<String>)
+  -> &'_ mut String
+{
+    let mut i = 0;
+    loop {
+        if i == v.len() {
+            break;
+        }
+        let s = &mut v[i];
+        if !s.is_empty() {
+            return s;
+        }
+        i += 1;
+    };
+    v.push(String::from("…"));
+    v.last_mut().unwrap()
+}
+
+// Not ours.
+mod unrelated {
+    fn polonius (polonius: i32)
+      -> i32
+    {
+        let _ = "polonius!(|x| -> &'polonius () { polonius_return!(x) })";
+        /* polonius_return!(…) */
+        ::core::convert::identity(r#polonius)
+    }
+}
@@ -0,0 +1,44 @@
+//! Path-qualified invocations, and other delimiters.
+
+use ::std::collections::HashMap;
+
+fn get_or_insert (mut map: &'_ mut HashMap<i32, String>)
+  -> &'_ String
+{
+    ::polonius_the_crab::polonius! { |map| -> &'polonius String {
+        if let Some(v) = map.get(&22) {
+            ::polonius_the_crab::polonius_return!(v);
+        }
+    }}
+    map.insert(22, String::from("…"));
+    &map[&22]
+}
+
+fn first_non_empty (mut v: &'_ mut Vec<String>)
+  -> &'_ mut String
+{
+    let mut i = 0;
+    polonius_the_crab::polonius_loop![|v| -> &'polonius mut String {
+        if i == v.len() {
+            polonius_the_crab::polonius_break!();
+        }
+        let s = &mut v[i];
+        if !s.is_empty() {
+            polonius_the_crab::polonius_return!(s);
+        }
+        i += 1;
+    }];
+    v.push(String::from("…"));
+    v.last_mut().unwrap()
+}
+
+// Not ours.
+mod unrelated {
+    fn polonius (polonius: i32)
+      -> i32
+    {
+        let _ = "polonius!(|x| -> &'polonius () { polonius_return!(x) })";
+        /* polonius_return!(…) */
+        ::core::convert::identity(r#polonius)
+    }
+}
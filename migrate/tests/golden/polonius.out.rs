@@ -0,0 +1,57 @@
+//! `polonius!`, with `polonius_return!` and `exit_polonius!`.
+
+use {
+    ::std::collections::HashMap,
+};
+
+fn get_or_insert (mut map: &'_ mut HashMap<i32, String>, key: i32)
+  -> &'_ String
+{
+    {
+        if let Some(v) = map.get(&key) {
+            return v;
+        }
+    };
+    map.insert(key, String::from("…"));
+    &map[&key]
+}
+
+fn exits (mut map: &'_ mut HashMap<i32, String>, key: i32)
+  -> &'_ str
+{
+    let len = 'exit_polonius: {
+        if map.is_empty() {
+            break 'exit_polonius 0;
+        }
+        if let Some(v) = map.get(&key) {
+            return v;
+        }
+        map.len()
+    };
+    'exit_polonius: {
+        if len > 42 {
+            break 'exit_polonius;
+        }
+    };
+    map.entry(key).or_default()
+}
+
+// Generic parameters.
+fn generic<'map, 'v, K, V : ?Sized> (
+    mut map: &'map mut HashMap<K, &'v V>,
+    key: &'_ K,
+    fallback_value: &'v V,
+) -> &'map &'v V
+where
+    K : ::core::hash::Hash + Eq + Clone,
+    V : ::core::fmt::Debug,
+{
+    {
+        if let Some(v) = map.get(key) {
+            dbg!(v);
+            return v;
+        }
+    };
+    map.insert(key.clone(), fallback_value);
+    &map[key]
+}
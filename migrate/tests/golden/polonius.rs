@@ -0,0 +1,58 @@
+//! `polonius!`, with `polonius_return!` and `exit_polonius!`.
+
+use {
+    ::polonius_the_crab::prelude::*,
+    ::std::collections::HashMap,
+};
+
+fn get_or_insert (mut map: &'_ mut HashMap<i32, String>, key: i32)
+  -> &'_ String
+{
+    polonius!(|map| -> &'polonius String {
+        if let Some(v) = map.get(&key) {
+            polonius_return!(v);
+        }
+    });
+    map.insert(key, String::from("…"));
+    &map[&key]
+}
+
+fn exits (mut map: &'_ mut HashMap<i32, String>, key: i32)
+  -> &'_ str
+{
+    let len = polonius!(|map| -> &'polonius str {
+        if map.is_empty() {
+            exit_polonius!(0);
+        }
+        if let Some(v) = map.get(&key) {
+            polonius_return!(v);
+        }
+        map.len()
+    });
+    polonius!(|map| -> &'polonius str {
+        if len > 42 {
+            exit_polonius!();
+        }
+    });
+    map.entry(key).or_default()
+}
+
+// Generic parameters.
+fn generic<'map, 'v, K, V : ?Sized> (
+    mut map: &'map mut HashMap<K, &'v V>,
+    key: &'_ K,
+    fallback_value: &'v V,
+) -> &'map &'v V
+where
+    K : ::core::hash::Hash + Eq + Clone,
+    V : ::core::fmt::Debug,
+{
+    polonius!(|map| -> &'polonius &'v V {
+        if let Some(v) = map.get(key) {
+            dbg!(v);
+            polonius_return!(v);
+        }
+    });
+    map.insert(key.clone(), fallback_value);
+    &map[key]
+}
@@ -0,0 +1,32 @@
+//! `dyn` flavors.
+
+use {
+    ::std::collections::HashMap,
+};
+
+fn get_or_insert (mut map: &'_ mut HashMap<i32, String>)
+  -> &'_ String
+{
+    {
+        if let Some(v) = map.get(&22) {
+            return v;
+        }
+    };
+    map.insert(22, String::from("…"));
+    &map[&22]
+}
+
+fn first_non_empty (mut map: &'_ mut HashMap<u8, String>)
+  -> Option<&'_ mut String>
+{
+    let mut i = 0;
+    loop {
+        match map.get_mut(&i) {
+            | Some(v) if !v.is_empty() => return Some(v),
+            | Some(_) => i += 1,
+            | None => break,
+        }
+    };
+    map.clear();
+    None
+}
@@ -0,0 +1,33 @@
+//! `dyn` flavors.
+
+use {
+    ::polonius_the_crab::prelude::*,
+    ::std::collections::HashMap,
+};
+
+fn get_or_insert (mut map: &'_ mut HashMap<i32, String>)
+  -> &'_ String
+{
+    polonius!(dyn |map| -> &'polonius String {
+        if let Some(v) = map.get(&22) {
+            polonius_return!(v);
+        }
+    });
+    map.insert(22, String::from("…"));
+    &map[&22]
+}
+
+fn first_non_empty (mut map: &'_ mut HashMap<u8, String>)
+  -> Option<&'_ mut String>
+{
+    let mut i = 0;
+    polonius_loop!(dyn |map| -> Option<&'polonius mut String> {
+        match map.get_mut(&i) {
+            | Some(v) if !v.is_empty() => polonius_return!(Some(v)),
+            | Some(_) => i += 1,
+            | None => polonius_break!(),
+        }
+    });
+    map.clear();
+    None
+}
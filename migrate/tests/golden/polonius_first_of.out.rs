@@ -0,0 +1,58 @@
+//! `polonius_first_of!`.
+
+use {
+    ::std::collections::HashMap,
+};
+
+fn lookup<'map> (
+    map: &'map mut HashMap<String, i32>,
+    primary: &str,
+    alias: &str,
+) -> &'map mut i32
+{
+    {
+        let input_borrow: &mut _ = map;
+        if let Some(hit) = { let m = &mut *input_borrow; m.get_mut(primary) } {
+            hit
+        } else if let Some(hit) = { let m = &mut *input_borrow; m.get_mut(alias) } {
+            hit
+        } else if let Some(hit) = { let m = &mut *input_borrow; m.get_mut("default") } {
+            hit
+        } else {
+            let m = input_borrow;
+            m.entry(primary.to_owned()).or_insert(0)
+        }
+    }
+}
+
+fn name<'map> (map: &'map mut HashMap<u8, String>, id: u8)
+  -> &'map str
+{
+    {
+        let input_borrow: &mut _ = map;
+        if let Some(hit) = { let m = &mut *input_borrow; m.get(&id).map(String::as_str) } {
+            hit
+        } else if let Some(hit) = { let m = &mut *input_borrow; m.get(&0).map(String::as_str) } {
+            hit
+        } else {
+            let m = input_borrow;
+            m.insert(0, "anonymous".into());
+            &m[&0]
+        }
+    }
+}
+
+fn single (map: &'_ mut HashMap<u8, String>)
+  -> &'_ mut String
+{
+    let value = {
+        let input_borrow: &mut _ = map;
+        if let Some(hit) = { let m = &mut *input_borrow; m.get_mut(&0) } {
+            hit
+        } else {
+            let m = input_borrow;
+            m.entry(0).or_default()
+        }
+    };
+    value
+}
@@ -0,0 +1,40 @@
+//! `polonius_first_of!`.
+
+use {
+    ::polonius_the_crab::prelude::*,
+    ::std::collections::HashMap,
+};
+
+fn lookup<'map> (
+    map: &'map mut HashMap<String, i32>,
+    primary: &str,
+    alias: &str,
+) -> &'map mut i32
+{
+    polonius_first_of!(map => [
+        |m| m.get_mut(primary),
+        |m| m.get_mut(alias),
+        |m| m.get_mut("default"),
+    ] else |m| {
+        m.entry(primary.to_owned()).or_insert(0)
+    })
+}
+
+fn name<'map> (map: &'map mut HashMap<u8, String>, id: u8)
+  -> &'map str
+{
+    polonius_first_of!(map => [
+        |m| m.get(&id).map(String::as_str),
+        |m| m.get(&0).map(String::as_str),
+    ] else |m| -> &'polonius str {
+        m.insert(0, "anonymous".into());
+        &m[&0]
+    })
+}
+
+fn single (map: &'_ mut HashMap<u8, String>)
+  -> &'_ mut String
+{
+    let value = polonius_first_of!(map => [|m| m.get_mut(&0)] else |m| { m.entry(0).or_default() });
+    value
+}
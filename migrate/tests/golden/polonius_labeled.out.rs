@@ -0,0 +1,42 @@
+//! Labeled-block flavor.
+
+use {
+    ::std::collections::HashMap,
+};
+
+fn greet (mut names: &'_ mut HashMap<u32, String>, id: u32)
+  -> usize
+{
+    let name: &String = 'found: {
+        {
+            if let Some(name) = names.get(&id) {
+                break 'found name;
+            }
+        };
+        names.entry(id).or_insert_with(|| format!("user #{id}"))
+    };
+    println!("Hello, {name}!");
+    name.len()
+}
+
+fn len_then_clear (map: &'_ mut HashMap<u8, String>, key: u8)
+  -> usize
+{
+    let value: &String = 'found: {
+        let mut map = &mut *map;
+        let missing = 'exit_polonius: {
+            if map.is_empty() {
+                break 'exit_polonius true;
+            }
+            if let Some(v) = map.get(&key) {
+                break 'found v;
+            }
+            false
+        };
+        assert!(!missing || map.is_empty());
+        map.entry(key).or_insert_with(|| "new".into())
+    };
+    let len = value.len();
+    map.clear();
+    len
+}
@@ -0,0 +1,43 @@
+//! Labeled-block flavor.
+
+use {
+    ::polonius_the_crab::prelude::*,
+    ::std::collections::HashMap,
+};
+
+fn greet (mut names: &'_ mut HashMap<u32, String>, id: u32)
+  -> usize
+{
+    let name: &String = 'found: {
+        polonius!('found, |names| -> &'polonius String {
+            if let Some(name) = names.get(&id) {
+                polonius_return!(name);
+            }
+        });
+        names.entry(id).or_insert_with(|| format!("user #{id}"))
+    };
+    println!("Hello, {name}!");
+    name.len()
+}
+
+fn len_then_clear (map: &'_ mut HashMap<u8, String>, key: u8)
+  -> usize
+{
+    let value: &String = 'found: {
+        let mut map = &mut *map;
+        let missing = polonius!('found, dyn |map| -> &'polonius String {
+            if map.is_empty() {
+                exit_polonius!(true);
+            }
+            if let Some(v) = map.get(&key) {
+                polonius_return!(v);
+            }
+            false
+        });
+        assert!(!missing || map.is_empty());
+        map.entry(key).or_insert_with(|| "new".into())
+    };
+    let len = value.len();
+    map.clear();
+    len
+}
@@ -0,0 +1,100 @@
+//! `polonius_loop!`, with `polonius_continue!`, `polonius_break!`, and
+//! `polonius_break_dependent!`.
+
+use {
+    ::polonius_the_crab::prelude::*,
+    ::std::collections::HashMap,
+};
+
+enum Value {
+    Alive(i32),
+    Daed,
+}
+
+fn get_first_alive_from_base_or_insert (
+    mut map: &'_ mut HashMap<usize, Value>,
+    base: usize,
+    default_value: i32,
+) -> &'_ i32
+{
+    let mut idx = base;
+    // (loop {
+    polonius_loop!(|map| -> &'polonius i32 {
+        use ::std::collections::hash_map::*;
+        // return(
+        polonius_return!(
+            match map.entry(idx) {
+                | Entry::Occupied(entry) => match entry.into_mut() {
+                    // Found a value!
+                    | &mut Value::Alive(ref val) => val,
+                    // "tombstone", keep searching
+                    | &mut Value::Daed => {
+                        idx += 1;
+                        // continue;
+                        polonius_continue!();
+                    },
+                },
+                | Entry::Vacant(slot) => match slot.insert(Value::Alive(default_value)) {
+                    | &mut Value::Alive(ref val) => val,
+                    | &mut Value::Daed => unreachable!(),
+                },
+            }
+        );
+    });
+    unreachable!();
+}
+
+fn break_entry (mut coll: &'_ mut HashMap<i32, String>)
+  -> &'_ mut String
+{
+    let mut i = 0;
+    let found = polonius_loop!(|coll| -> _, break: &'polonius mut String {
+        match coll.get_mut(&i) {
+            Some(entry) => if entry.is_empty() {
+                polonius_break_dependent!(entry);
+            } else {
+                // …
+            },
+            None => i += 1,
+        }
+    });
+    found.push('!');
+    found
+}
+
+fn break_index (mut coll: &'_ mut HashMap<i32, String>)
+  -> &'_ mut String
+{
+    let mut i = 0;
+    let position = polonius_loop!(|coll| -> &'polonius mut String {
+        match coll.get_mut(&i) {
+            Some(entry) => if entry.is_empty() {
+                polonius_break!(i);
+            } else {
+                polonius_return!(entry);
+            },
+            None => i += 1,
+        }
+    });
+    assert_eq!(position, i);
+    coll.get_mut(&i).unwrap()
+}
+
+// `polonius_break!` and `polonius_continue!` within an inner loop.
+fn nested_loops (mut rows: &'_ mut Vec<Vec<String>>)
+  -> &'_ mut String
+{
+    let mut i = 0;
+    polonius_loop!(|rows| -> &'polonius mut String {
+        let Some(row) = rows.get_mut(i) else { polonius_break!() };
+        i += 1;
+        for cell in row {
+            if cell.is_empty() {
+                polonius_continue!();
+            }
+            polonius_return!(cell);
+        }
+    });
+    rows.push(vec![String::new()]);
+    &mut rows[i][0]
+}
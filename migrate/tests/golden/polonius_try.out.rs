@@ -0,0 +1,59 @@
+//! `polonius_try!`.
+
+use {
+    ::std::collections::HashMap,
+};
+
+enum Error { /* … */ }
+
+fn fallible_operation (_: &'_ i32)
+  -> Result<(), Error>
+{
+    Ok(())
+}
+
+fn get_or_insert (
+    mut map: &'_ mut HashMap<i32, i32>,
+) -> Result<&'_ i32, Error>
+{
+    {
+        if let Some(value) = map.get(&22) {
+            // fallible_operation(value)?;
+            fallible_operation(value)?;
+            return Ok(value);
+        }
+    };
+    map.insert(22, 42);
+    Ok(&map[&22])
+}
+
+fn first_char (mut map: &'_ mut HashMap<u8, String>, key: u8)
+  -> Option<&'_ str>
+{
+    {
+        if let Some(v) = map.get(&key) {
+            let c = v.chars().next()?;
+            let _: usize = (if c.is_ascii() { Some(1) } else { None })?;
+            return Some(&v[.. c.len_utf8()]);
+        }
+    };
+    map.clear();
+    None
+}
+
+fn in_loop (mut map: &'_ mut HashMap<u8, String>)
+  -> Result<&'_ str, ::core::num::ParseIntError>
+{
+    let mut key = 0;
+    loop {
+        if let Some(v) = map.get(&key) {
+            key = v.parse::<u8>()?;
+            if key == 0 {
+                return Ok(v);
+            }
+        } else {
+            break;
+        }
+    };
+    Ok(map.entry(key).or_default())
+}
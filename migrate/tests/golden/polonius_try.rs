@@ -0,0 +1,60 @@
+//! `polonius_try!`.
+
+use {
+    ::polonius_the_crab::prelude::*,
+    ::std::collections::HashMap,
+};
+
+enum Error { /* … */ }
+
+fn fallible_operation (_: &'_ i32)
+  -> Result<(), Error>
+{
+    Ok(())
+}
+
+fn get_or_insert (
+    mut map: &'_ mut HashMap<i32, i32>,
+) -> Result<&'_ i32, Error>
+{
+    polonius!(|map| -> Result<&'polonius i32, Error> {
+        if let Some(value) = map.get(&22) {
+            // fallible_operation(value)?;
+            polonius_try!(fallible_operation(value));
+            polonius_return!(Ok(value));
+        }
+    });
+    map.insert(22, 42);
+    Ok(&map[&22])
+}
+
+fn first_char (mut map: &'_ mut HashMap<u8, String>, key: u8)
+  -> Option<&'_ str>
+{
+    polonius!(|map| -> Option<&'polonius str> {
+        if let Some(v) = map.get(&key) {
+            let c = polonius_try!(v.chars().next());
+            let _: usize = polonius_try!(if c.is_ascii() { Some(1) } else { None });
+            polonius_return!(Some(&v[.. c.len_utf8()]));
+        }
+    });
+    map.clear();
+    None
+}
+
+fn in_loop (mut map: &'_ mut HashMap<u8, String>)
+  -> Result<&'_ str, ::core::num::ParseIntError>
+{
+    let mut key = 0;
+    polonius_loop!(|map| -> Result<&'polonius str, ::core::num::ParseIntError> {
+        if let Some(v) = map.get(&key) {
+            key = polonius_try!(v.parse::<u8>());
+            if key == 0 {
+                polonius_return!(Ok(v));
+            }
+        } else {
+            polonius_break!();
+        }
+    });
+    Ok(map.entry(key).or_default())
+}
@@ -0,0 +1,65 @@
+//! Sites which cannot be translated, and are left as they are.
+
+use {
+    ::polonius_the_crab::prelude::*,
+    ::std::collections::HashMap,
+};
+
+fn try_in_labeled (map: &'_ mut HashMap<u8, String>)
+  -> Option<usize>
+{
+    let value: &String = 'found: {
+        let mut map = &mut *map;
+        polonius!('found, |map| -> Option<&'polonius String> {
+            if let Some(v) = map.get(&0) {
+                let _ = polonius_try!(v.chars().next());
+                polonius_return!(v);
+            }
+        });
+        map.entry(0).or_default()
+    };
+    Some(value.len())
+}
+
+fn translated_fine (mut map: &'_ mut HashMap<u8, String>)
+  -> &'_ String
+{
+    polonius!(|map| -> &'polonius String {
+        if let Some(v) = map.get(&0) {
+            polonius_return!(v);
+        }
+    });
+    map.entry(0).or_default()
+}
+
+macro_rules! get_or_default {( $map:expr, $key:expr ) => ({
+    let mut map = $map;
+    polonius!(|map| -> &'polonius String {
+        if let Some(v) = map.get(&$key) {
+            polonius_return!(v);
+        }
+    });
+    map.entry($key).or_default()
+})}
+
+macro_rules! ours {( $var:ident ) => (
+    polonius!(|$var| -> &'polonius String {
+        polonius_return!(&$var[&0]);
+    })
+)}
+
+fn misused (mut map: &'_ mut HashMap<u8, String>)
+  -> &'_ String
+{
+    polonius_loop!(|map| -> &'polonius String {
+        if map.is_empty() {
+            exit_polonius!();
+        }
+    });
+    polonius!(|map| -> &'polonius String {
+        if map.is_empty() {
+            polonius_break!();
+        }
+    });
+    polonius_return!(get_or_default!(map, 0))
+}
@@ -0,0 +1,127 @@
+/// Asserts that a [`PoloniusResult`][crate::PoloniusResult] is the
+/// [`Borrowing`][crate::PoloniusResult::Borrowing] variant, and evaluates to
+/// its dependent value.
+///
+/// An optional pattern (with an optional `if` guard) can be given for the
+/// dependent value to be checked against, as with
+/// <code>[matches!]\(&dependent, …)</code>.
+///
+/// ## Example
+///
+/**  - ```rust
+    use ::polonius_the_crab::{assert_borrowing, polonius, ForLt, PoloniusResult};
+
+    let mut v = vec![1, 2, 3];
+    let first = assert_borrowing!(
+        polonius::<_, _, ForLt!(<'r> = &'r mut i32)>(&mut v, |v| {
+            match v.first_mut() {
+                | Some(first) => PoloniusResult::Borrowing(first),
+                | None => PoloniusResult::Owned(()),
+            }
+        }),
+        1,
+    );
+    *first = 0;
+    assert_eq!(v, [0, 2, 3]);
+    ``` */
+///
+/// ## Panics
+///
+/// With a message naming the variant actually encountered, or, if the variant
+/// is the right one, stating that its payload did not match the pattern.
+#[macro_export]
+macro_rules! assert_borrowing {
+    (
+        $e:expr $(,)?
+    ) => (
+        $crate::assert_borrowing!($e, _)
+    );
+
+    (
+        $e:expr, $($pat:pat_param)|+ $(if $guard:expr)? $(,)?
+    ) => (
+        match $e {
+            | $crate::PoloniusResult::Borrowing(dependent) => {
+                match &dependent {
+                    | $($pat)|+ $(if $guard)? => {},
+                    | _ => $crate::ඞ::core::panic!(
+                        "assertion failed: `{}` is `PoloniusResult::Borrowing(…)`, \
+                        but its dependent value does not match `{}`",
+                        $crate::ඞ::core::stringify!($e),
+                        $crate::ඞ::core::stringify!($($pat)|+ $(if $guard)?),
+                    ),
+                }
+                dependent
+            },
+            | $crate::PoloniusResult::Owned { .. } => $crate::ඞ::core::panic!(
+                "assertion failed: `{}` is `PoloniusResult::Owned {{ … }}`, \
+                expected `PoloniusResult::Borrowing(…)`",
+                $crate::ඞ::core::stringify!($e),
+            ),
+        }
+    );
+}
+
+/// Asserts that a [`PoloniusResult`][crate::PoloniusResult] is the
+/// [`Owned`][type@crate::PoloniusResult::Owned] variant, and evaluates to its
+/// `input_borrow`, so that it may be used further.
+///
+/// An optional pattern (with an optional `if` guard) can be given for the
+/// owned `value` to be checked against, as with
+/// <code>[matches!]\(&value, …)</code>.
+///
+/// ## Example
+///
+/**  - ```rust
+    use ::polonius_the_crab::{assert_owned, polonius, ForLt, PoloniusResult};
+
+    let mut v = vec![];
+    let v = assert_owned!(
+        polonius::<_, _, ForLt!(<'r> = &'r mut i32)>(&mut v, |v| {
+            let len = v.len();
+            match v.first_mut() {
+                | Some(first) => PoloniusResult::Borrowing(first),
+                | None => PoloniusResult::Owned(len),
+            }
+        }),
+        0,
+    );
+    v.push(42);
+    ``` */
+///
+/// ## Panics
+///
+/// With a message naming the variant actually encountered, or, if the variant
+/// is the right one, stating that its `value` did not match the pattern.
+#[macro_export]
+macro_rules! assert_owned {
+    (
+        $e:expr $(,)?
+    ) => (
+        $crate::assert_owned!($e, _)
+    );
+
+    (
+        $e:expr, $($pat:pat_param)|+ $(if $guard:expr)? $(,)?
+    ) => (
+        match $e {
+            | $crate::PoloniusResult::Owned { value, input_borrow } => {
+                match &value {
+                    | $($pat)|+ $(if $guard)? => {},
+                    | _ => $crate::ඞ::core::panic!(
+                        "assertion failed: `{}` is `PoloniusResult::Owned {{ … }}`, \
+                        but its `value` does not match `{}`",
+                        $crate::ඞ::core::stringify!($e),
+                        $crate::ඞ::core::stringify!($($pat)|+ $(if $guard)?),
+                    ),
+                }
+                input_borrow
+            },
+            | $crate::PoloniusResult::Borrowing(_) => $crate::ඞ::core::panic!(
+                "assertion failed: `{}` is `PoloniusResult::Borrowing(…)`, \
+                expected `PoloniusResult::Owned {{ … }}`",
+                $crate::ඞ::core::stringify!($e),
+            ),
+        }
+    );
+}
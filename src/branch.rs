@@ -0,0 +1,179 @@
+//! Named (trait-based) flavor of the `branch` closure of [`polonius()`].
+
+use {
+    ::core::marker::PhantomData,
+    crate::{
+        ForLt,
+        polonius,
+        PoloniusResult,
+    },
+};
+
+/// A nameable `branch` for [`polonius_with()`]: what the closure given to
+/// [`polonius()`] does, but as a type which can be stored in a struct field,
+/// or be a generic parameter (_e.g._, a family of lookup strategies).
+///
+/// The output types are associated types, the dependent one being a
+/// [`ForLt!`] type, as with the turbofished parameter of [`polonius()`].
+///
+/// ## Example
+///
+/**  - ```rust
+    use {
+        ::polonius_the_crab::{ForLt, polonius_with, PoloniusBranch, PoloniusResult},
+        ::std::collections::BTreeMap,
+    };
+
+    type Map = BTreeMap<String, u32>;
+
+    /// The value at `key`.
+    struct Exact<'k>(&'k str);
+
+    /// The value of the first key starting with the given prefix.
+    struct Prefixed<'p>(&'p str);
+
+    impl PoloniusBranch<Map> for Exact<'_> {
+        type Borrowing = ForLt!(<'r> = &'r mut u32);
+        type Owned = ();
+
+        fn branch (self, map: &'_ mut Map)
+          -> PoloniusResult<&'_ mut u32, ()>
+        {
+            match map.get_mut(self.0) {
+                | Some(v) => PoloniusResult::Borrowing(v),
+                | None => PoloniusResult::Owned(()),
+            }
+        }
+    }
+
+    impl PoloniusBranch<Map> for Prefixed<'_> {
+        type Borrowing = ForLt!(<'r> = &'r mut u32);
+        type Owned = ();
+
+        fn branch (self, map: &'_ mut Map)
+          -> PoloniusResult<&'_ mut u32, ()>
+        {
+            match map.range_mut(self.0.to_owned() ..).next() {
+                | Some((k, v)) if k.starts_with(self.0) => PoloniusResult::Borrowing(v),
+                | _ => PoloniusResult::Owned(()),
+            }
+        }
+    }
+
+    /// Generic over the lookup strategy.
+    fn get_or_zero<'m, S> (map: &'m mut Map, strategy: S, key: &str)
+      -> &'m mut u32
+    where
+        S : PoloniusBranch<Map, Borrowing = ForLt!(<'r> = &'r mut u32), Owned = ()>,
+    {
+        match polonius_with(map, strategy) {
+            | PoloniusResult::Borrowing(v) => v,
+            | PoloniusResult::Owned { input_borrow: map, .. } => {
+                map.entry(key.to_owned()).or_default()
+            },
+        }
+    }
+
+    let mut map = Map::from([("crab".into(), 1)]);
+    *get_or_zero(&mut map, Prefixed("cr"), "cr") += 1;
+    *get_or_zero(&mut map, Exact("cr"), "cr") += 1;
+    assert_eq!(map, Map::from([("crab".into(), 2), ("cr".into(), 1)]));
+    ``` */
+///
+/// Closures can be used as well, through [`branch_fn()`].
+///
+/// [`ForLt!`]: crate::ForLt!
+pub
+trait PoloniusBranch<Input : ?Sized> {
+    /// The [`ForLt!`][crate::ForLt!] type of the output of the
+    /// [`PoloniusResult::Borrowing`] case, _i.e._, dependent on the borrow
+    /// of the `Input`.
+    type Borrowing : ?Sized + ForLt;
+
+    /// The output of the [`PoloniusResult::Owned`][type@PoloniusResult::Owned]
+    /// case.
+    type Owned;
+
+    /// What the closure given to [`polonius()`] would do.
+    fn branch (self, input: &'_ mut Input)
+      -> PoloniusResult<
+            <Self::Borrowing as ForLt>::Of<'_>,
+            Self::Owned,
+        >
+    ;
+}
+
+/// A [`PoloniusBranch`] made out of a closure: see [`branch_fn()`].
+pub
+struct BranchFn<Borrowing : ?Sized, Owned, F> {
+    f: F,
+    _borrowing: PhantomData<fn() -> *const Borrowing>,
+    _owned: PhantomData<fn() -> Owned>,
+}
+
+/// Wraps a closure into a [`PoloniusBranch`].
+///
+/// The [`Self::Borrowing`][PoloniusBranch::Borrowing] type of a closure cannot
+/// be inferred: as with [`polonius()`], it has to be turbofished, as a
+/// [`ForLt!`][crate::ForLt!] type. The `Input` not being known until the
+/// [`BranchFn`] is used, the closure parameter may need a type annotation.
+///
+/**  - ```rust
+    use ::polonius_the_crab::{branch_fn, ForLt, polonius_with, PoloniusResult};
+
+    let mut v = vec![1, 2, 3];
+    let last_even = branch_fn::<ForLt!(<'r> = &'r mut i32), _, _, _>(|v: &mut Vec<i32>| {
+        match v.iter_mut().rev().find(|x| **x % 2 == 0) {
+            | Some(x) => PoloniusResult::Borrowing(x),
+            | None => PoloniusResult::Owned(()),
+        }
+    });
+    match polonius_with(&mut v, last_even) {
+        | PoloniusResult::Borrowing(x) => *x *= 10,
+        | PoloniusResult::Owned { .. } => unreachable!(),
+    }
+    assert_eq!(v, [1, 20, 3]);
+    ``` */
+pub
+fn branch_fn<Borrowing : ?Sized, Input : ?Sized, Owned, F> (f: F)
+  -> BranchFn<Borrowing, Owned, F>
+where
+    Borrowing : ForLt,
+    F : for<'any> FnOnce(&'any mut Input) -> PoloniusResult<Borrowing::Of<'any>, Owned>,
+{
+    BranchFn { f, _borrowing: PhantomData, _owned: PhantomData }
+}
+
+impl<Input : ?Sized, Borrowing : ?Sized, Owned, F> PoloniusBranch<Input>
+    for BranchFn<Borrowing, Owned, F>
+where
+    Borrowing : ForLt,
+    F : for<'any> FnOnce(&'any mut Input) -> PoloniusResult<Borrowing::Of<'any>, Owned>,
+{
+    type Borrowing = Borrowing;
+    type Owned = Owned;
+
+    fn branch (self, input: &'_ mut Input)
+      -> PoloniusResult<Borrowing::Of<'_>, Owned>
+    {
+        (self.f)(input)
+    }
+}
+
+/// [`polonius()`], but with a [`PoloniusBranch`] rather than a closure.
+///
+/// The output types being those of the `branch`, there is nothing to turbofish.
+#[cfg_attr(feature = "instrument", track_caller)]
+pub
+fn polonius_with<Input : ?Sized, Branch> (
+    input_borrow: &'_ mut Input,
+    branch: Branch,
+) -> PoloniusResult<
+        <Branch::Borrowing as ForLt>::Of<'_>,
+        Branch::Owned, &'_ mut Input,
+    >
+where
+    Branch : PoloniusBranch<Input>,
+{
+    polonius::<_, _, Branch::Borrowing>(input_borrow, |it| branch.branch(it))
+}
@@ -0,0 +1,285 @@
+//! Helpers for the "borrow a frame from the buffer, or refill it and retry"
+//! loop of buffered decoders.
+//!
+//! The following, for instance, fails to compile without Polonius, since the
+//! `return`ed `frame` keeps `*self` borrowed across the whole `loop`:
+//!
+/*!  - ```rust ,compile_fail
+    # use ::polonius_the_crab::buf::BufferedSource;
+    # fn parse (_: &[u8]) -> Option<usize> { None }
+    fn read_frame<S : BufferedSource> (source: &'_ mut S)
+      -> Result<&'_ [u8], S::Error>
+    {
+        loop {
+            let buffered = source.buffered();
+            if let Some(len) = parse(buffered) {
+                return Ok(&buffered[.. len]);
+            }
+            source.fill_more()?;
+        }
+    }
+    ``` */
+//!
+//! Hence the [`BufferedSource::read_frame()`] and
+//! [`BufferedSource::read_line_borrowed()`] helpers, implemented with a
+//! `loop { polonius!(…); … }`.
+//!
+//!   - Note that this is one of the loops which [cannot be expressed as a
+//!     `polonius_loop!`][l], since the input is refilled _after_ the
+//!     tentative borrow, within the same iteration.
+//!
+//! [l]: https://github.com/danielhenrymantilla/polonius-the-crab.rs/issues/11
+//!
+//! ## Example
+//!
+/*!  - ```rust
+    use ::polonius_the_crab::buf::BufferedSource;
+
+    /// Frames are prefixed with their length, as a single byte.
+    fn length_prefixed (buffered: &[u8]) -> Option<usize> {
+        let (&len, payload) = buffered.split_first()?;
+        (payload.len() >= len.into()).then(|| 1 + usize::from(len))
+    }
+
+    let mut source: &[u8] = b"\x03abc\x01d\x05ef";
+    let frame = source.read_frame(length_prefixed).unwrap().unwrap();
+    assert_eq!(frame, b"\x03abc");
+    let len = frame.len();
+    source.consume(len);
+    assert_eq!(source.read_frame(length_prefixed).unwrap(), Some(&b"\x01d"[..]));
+    source.consume(2);
+    // incomplete frame, and no more data.
+    assert_eq!(source.read_frame(length_prefixed).unwrap(), None);
+    ``` */
+
+use {
+    ::core::convert::Infallible,
+    crate::{
+        polonius,
+        polonius_return,
+    },
+};
+
+#[cfg(feature = "std")]
+pub use io::IoSource;
+
+/// A source of bytes with an internal buffer which can be [refilled][
+/// Self::fill_more] without losing the bytes buffered so far.
+///
+/// Mirrors [`std::io::BufRead`], except for [`.fill_more()`][Self::fill_more]
+/// which _appends_ to the buffer rather than only reading when empty: this is
+/// what lets frames straddling reads be borrowed in one piece.
+pub
+trait BufferedSource {
+    /// The error type of the underlying reads.
+    type Error;
+
+    /// The buffered bytes which have not been [consumed][Self::consume] yet.
+    fn buffered (&self)
+      -> &[u8]
+    ;
+
+    /// Reads more bytes, appending them to the [buffered][Self::buffered]
+    /// ones.
+    ///
+    /// Returns how many bytes were read: `0` signals the end of the input.
+    fn fill_more (&mut self)
+      -> Result<usize, Self::Error>
+    ;
+
+    /// Discards the first `amount` [buffered][Self::buffered] bytes.
+    ///
+    /// ## Panics
+    ///
+    /// If `amount` exceeds the number of buffered bytes.
+    fn consume (&mut self, amount: usize)
+    ;
+
+    /// Returns the first frame found in the buffered bytes, reading more of
+    /// them until `parse` finds one.
+    ///
+    /// `parse` is given all the buffered bytes, and returns the length of the
+    /// frame they start with, if complete.
+    ///
+    /// Returns `Ok(None)` when the input ends before a full frame is found
+    /// (the leftover bytes remain [buffered][Self::buffered]).
+    ///
+    /// Like with [`std::io::BufRead::fill_buf()`], the returned frame is
+    /// **not** consumed: call <code>.[consume]\(frame.len())</code> once done
+    /// with it.
+    ///
+    /// ## Panics
+    ///
+    /// If `parse` returns a length exceeding the number of bytes it was given.
+    ///
+    /// [consume]: Self::consume
+    fn read_frame (
+        &mut self,
+        mut parse: impl FnMut(&[u8]) -> Option<usize>,
+    ) -> Result<Option<&[u8]>, Self::Error>
+    {
+        let mut this = self;
+        loop {
+            polonius!(|this| -> Result<Option<&'polonius [u8]>, Self::Error> {
+                let buffered = this.buffered();
+                if let Some(len) = parse(buffered) {
+                    assert!(
+                        len <= buffered.len(),
+                        "`parse` returned a frame length of {len}, \
+                        but was only given {} bytes",
+                        buffered.len(),
+                    );
+                    polonius_return!(Ok(Some(&buffered[.. len])));
+                }
+            });
+            if this.fill_more()? == 0 {
+                return Ok(None);
+            }
+        }
+    }
+
+    /// Returns the next line, `\n` terminator included, reading more bytes
+    /// until one is found.
+    ///
+    /// At the end of the input, the leftover bytes make up the last line,
+    /// and once there are none, `Ok(None)` is returned.
+    ///
+    /// As with [`Self::read_frame()`], the returned line is **not** consumed.
+    fn read_line_borrowed (&mut self)
+      -> Result<Option<&[u8]>, Self::Error>
+    {
+        let mut this = self;
+        // avoid rescanning the bytes already known not to contain a `\n`.
+        let mut searched = 0;
+        loop {
+            polonius!(|this| -> Result<Option<&'polonius [u8]>, Self::Error> {
+                let buffered = this.buffered();
+                if let Some(idx) = buffered[searched ..].iter().position(|&b| b == b'\n') {
+                    polonius_return!(Ok(Some(&buffered[..= searched + idx])));
+                }
+                searched = buffered.len();
+            });
+            if this.fill_more()? == 0 {
+                let leftover = this.buffered();
+                return Ok(if leftover.is_empty() { None } else { Some(leftover) });
+            }
+        }
+    }
+}
+
+/// An in-memory source: everything is already buffered.
+impl BufferedSource for &'_ [u8] {
+    type Error = Infallible;
+
+    #[inline]
+    fn buffered (&self)
+      -> &[u8]
+    {
+        self
+    }
+
+    #[inline]
+    fn fill_more (&mut self)
+      -> Result<usize, Infallible>
+    {
+        Ok(0)
+    }
+
+    #[inline]
+    fn consume (&mut self, amount: usize)
+    {
+        *self = &self[amount ..];
+    }
+}
+
+#[cfg(feature = "std")]
+mod io {
+    use {
+        ::std::{
+            io::{self, Read},
+            vec::Vec,
+        },
+        super::BufferedSource,
+    };
+
+    /// A [`BufferedSource`] over a [`Read`]er.
+    pub
+    struct IoSource<R> {
+        reader: R,
+        buffer: Vec<u8>,
+        /// How many bytes at the beginning of `buffer` have been consumed.
+        start: usize,
+        chunk_size: usize,
+    }
+
+    impl<R : Read> IoSource<R> {
+        /// Wraps `reader`, reading (at most) 8 KiB from it at a time.
+        pub
+        fn new (reader: R)
+          -> Self
+        {
+            Self::with_chunk_size(8 * 1024, reader)
+        }
+
+        /// Wraps `reader`, reading (at most) `chunk_size` bytes from it at a
+        /// time.
+        pub
+        fn with_chunk_size (chunk_size: usize, reader: R)
+          -> Self
+        {
+            assert!(chunk_size > 0, "`chunk_size` must not be zero");
+            Self {
+                reader,
+                buffer: Vec::new(),
+                start: 0,
+                chunk_size,
+            }
+        }
+
+        /// Unwraps the inner reader, discarding the buffered bytes.
+        pub
+        fn into_inner (self)
+          -> R
+        {
+            self.reader
+        }
+    }
+
+    impl<R : Read> BufferedSource for IoSource<R> {
+        type Error = io::Error;
+
+        #[inline]
+        fn buffered (&self)
+          -> &[u8]
+        {
+            &self.buffer[self.start ..]
+        }
+
+        fn fill_more (&mut self)
+          -> io::Result<usize>
+        {
+            // Reclaim the space of the consumed bytes.
+            self.buffer.drain(.. self.start);
+            self.start = 0;
+            let len = self.buffer.len();
+            self.buffer.resize(len + self.chunk_size, 0);
+            let res = loop {
+                match self.reader.read(&mut self.buffer[len ..]) {
+                    | Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                    | res => break res,
+                }
+            };
+            self.buffer.truncate(len + *res.as_ref().unwrap_or(&0));
+            res
+        }
+
+        fn consume (&mut self, amount: usize)
+        {
+            assert!(
+                amount <= self.buffered().len(),
+                "cannot consume more bytes than buffered",
+            );
+            self.start += amount;
+        }
+    }
+}
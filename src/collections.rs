@@ -0,0 +1,129 @@
+//! Lookups into the standard maps by _borrowed_ key, which only allocate the
+//! owned key on a miss.
+//!
+//! The `.entry()` API requires an owned key upfront, _i.e._, allocating on
+//! every call (even the hits!), whereas the allocation-free version does not
+//! compile without Polonius:
+//!
+/*!  - ```rust ,compile_fail
+    use ::std::collections::BTreeMap;
+
+    fn get_or_insert<'r> (map: &'r mut BTreeMap<String, u32>, key: &str)
+      -> &'r mut u32
+    {
+        if let Some(value) = map.get_mut(key) {
+            return value;
+        }
+        map.entry(key.to_owned()).or_insert(0)
+    }
+    ``` */
+//!
+//! Hence [`GetOrInsertBorrowed`].
+
+use {
+    ::alloc::{
+        borrow::ToOwned,
+        collections::BTreeMap,
+    },
+    ::core::borrow::Borrow,
+    crate::{
+        ForLt,
+        polonius,
+        PoloniusResult,
+    },
+};
+
+/// Maps which can be looked up with a borrowed `&Q` key, and only need the
+/// owned key (<code>Q::[Owned][ToOwned::Owned]</code>) when inserting.
+///
+/// Implemented for [`BTreeMap`] and, with the `std` feature,
+/// [`HashMap`][::std::collections::HashMap].
+///
+/// ## Example
+///
+/**  - ```rust
+    use {
+        ::polonius_the_crab::collections::GetOrInsertBorrowed,
+        ::std::collections::BTreeMap,
+    };
+
+    let mut word_counts = BTreeMap::<String, usize>::new();
+    for word in "the cat and the hat".split(' ') {
+        // `word.to_owned()` only happens for the first `the`.
+        *word_counts.get_or_insert_borrowed(word, |_| 0) += 1;
+    }
+    assert_eq!(word_counts["the"], 2);
+    assert_eq!(word_counts.len(), 4);
+    ``` */
+pub
+trait GetOrInsertBorrowed<Q : ?Sized + ToOwned> {
+    /// The type of the values of the map.
+    type Value;
+
+    /// Returns the value for `key`, inserting `make_value(key)` under
+    /// `key.to_owned()` on a miss.
+    fn get_or_insert_borrowed (
+        &mut self,
+        key: &'_ Q,
+        make_value: impl FnOnce(&Q) -> Self::Value,
+    ) -> &'_ mut Self::Value
+    ;
+}
+
+impl<K, V, Q> GetOrInsertBorrowed<Q> for BTreeMap<K, V>
+where
+    K : Ord + Borrow<Q>,
+    Q : ?Sized + Ord + ToOwned<Owned = K>,
+{
+    type Value = V;
+
+    fn get_or_insert_borrowed (
+        &mut self,
+        key: &'_ Q,
+        make_value: impl FnOnce(&Q) -> V,
+    ) -> &'_ mut V
+    {
+        match polonius::<_, _, ForLt!(<'r> = &'r mut V)>(self, |map| {
+            match map.get_mut(key) {
+                | Some(value) => PoloniusResult::Borrowing(value),
+                | None => PoloniusResult::Owned(()),
+            }
+        })
+        {
+            | PoloniusResult::Borrowing(value) => value,
+            | PoloniusResult::Owned { input_borrow: map, .. } => {
+                map.entry(key.to_owned()).or_insert_with(|| make_value(key))
+            },
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S, Q> GetOrInsertBorrowed<Q> for ::std::collections::HashMap<K, V, S>
+where
+    K : ::core::hash::Hash + Eq + Borrow<Q>,
+    S : ::core::hash::BuildHasher,
+    Q : ?Sized + ::core::hash::Hash + Eq + ToOwned<Owned = K>,
+{
+    type Value = V;
+
+    fn get_or_insert_borrowed (
+        &mut self,
+        key: &'_ Q,
+        make_value: impl FnOnce(&Q) -> V,
+    ) -> &'_ mut V
+    {
+        match polonius::<_, _, ForLt!(<'r> = &'r mut V)>(self, |map| {
+            match map.get_mut(key) {
+                | Some(value) => PoloniusResult::Borrowing(value),
+                | None => PoloniusResult::Owned(()),
+            }
+        })
+        {
+            | PoloniusResult::Borrowing(value) => value,
+            | PoloniusResult::Owned { input_borrow: map, .. } => {
+                map.entry(key.to_owned()).or_insert_with(|| make_value(key))
+            },
+        }
+    }
+}
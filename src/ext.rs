@@ -0,0 +1,147 @@
+//! Method-syntax flavor of the common "lookup, or else fallback" [`polonius()`]
+//! calls.
+
+use crate::{
+    ForLt,
+    polonius,
+    PoloniusResult,
+    Fallible,
+    Residual,
+    Try,
+};
+
+/// Extension trait to perform [`polonius()`] lookups with method syntax, on a
+/// `&mut` borrow, without a macro nor a [`ForLt!`][crate::ForLt!] turbofish.
+///
+/// It is also part of the [`prelude`][crate::prelude].
+///
+/// ## Example
+///
+/**  - ```rust
+    use {
+        ::polonius_the_crab::PoloniusExt,
+        ::std::collections::HashMap,
+    };
+
+    fn get_or_insert<'m> (map: &'m mut HashMap<u32, String>, key: u32)
+      -> &'m mut String
+    {
+        map.polonius_get(|m| m.get_mut(&key))
+            .or_else(|m| m.get_mut(&0))
+            .or_insert_with(|m| m.entry(key).or_insert_with(|| key.to_string()))
+    }
+
+    let mut map = HashMap::new();
+    get_or_insert(&mut map, 1).push('!');
+    assert_eq!(map[&1], "1!");
+    map.insert(0, "default".into());
+    get_or_insert(&mut map, 2).push('!');
+    assert_eq!(map[&0], "default!");
+    assert_eq!(map.get(&2), None);
+    ``` */
+pub
+trait PoloniusExt {
+    /// Starts a [`PoloniusGet`] chain with the given `lookup`: either it
+    /// finds something, borrowing from `self`, or we get `self` back, to try
+    /// something else.
+    fn polonius_get<U : ?Sized> (
+        &'_ mut self,
+        lookup: impl for<'n> FnOnce(&'n mut Self) -> Option<&'n mut U>,
+    ) -> PoloniusGet<'_, Self, U>
+    ;
+}
+
+impl<T : ?Sized> PoloniusExt for T {
+    #[cfg_attr(feature = "instrument", track_caller)]
+    fn polonius_get<U : ?Sized> (
+        &'_ mut self,
+        lookup: impl for<'n> FnOnce(&'n mut T) -> Option<&'n mut U>,
+    ) -> PoloniusGet<'_, T, U>
+    {
+        PoloniusGet { found: get(self, lookup) }
+    }
+}
+
+#[cfg_attr(feature = "instrument", track_caller)]
+fn get<'r, T : ?Sized, U : ?Sized> (
+    input: &'r mut T,
+    lookup: impl for<'n> FnOnce(&'n mut T) -> Option<&'n mut U>,
+) -> Result<&'r mut U, &'r mut T>
+{
+    match polonius::<_, _, ForLt!(<'n> = &'n mut U)>(input, |input| {
+        match lookup(input) {
+            | Some(found) => PoloniusResult::Borrowing(found),
+            | None => PoloniusResult::Owned(()),
+        }
+    }) {
+        | PoloniusResult::Borrowing(found) => Ok(found),
+        | PoloniusResult::Owned { input_borrow, .. } => Err(input_borrow),
+    }
+}
+
+/// The ongoing lookup chain started by [`PoloniusExt::polonius_get()`].
+///
+/// Each lookup is performed eagerly; the methods of this type only decide what
+/// to do on a miss.
+pub
+struct PoloniusGet<'r, T : ?Sized, U : ?Sized> {
+    found: Result<&'r mut U, &'r mut T>,
+}
+
+impl<'r, T : ?Sized, U : ?Sized> PoloniusGet<'r, T, U> {
+    /// On a miss, tries another `lookup`.
+    #[cfg_attr(feature = "instrument", track_caller)]
+    pub
+    fn or_else (
+        self,
+        lookup: impl for<'n> FnOnce(&'n mut T) -> Option<&'n mut U>,
+    ) -> Self
+    {
+        match self.found {
+            | Ok(found) => PoloniusGet { found: Ok(found) },
+            | Err(input) => PoloniusGet { found: get(input, lookup) },
+        }
+    }
+
+    /// On a miss, falls back to `insert`, which is given the input back.
+    pub
+    fn or_insert_with (
+        self,
+        insert: impl FnOnce(&'r mut T) -> &'r mut U,
+    ) -> &'r mut U
+    {
+        match self.found {
+            | Ok(found) => found,
+            | Err(input) => insert(input),
+        }
+    }
+
+    /// Fallible flavor of [`.or_insert_with()`][Self::or_insert_with]: on a
+    /// miss, falls back to `fallback`, whose `?`-failure is propagated.
+    ///
+    /// `fallback` may return an [`Option`] or a [`Result`].
+    pub
+    fn or_try_else<R> (
+        self,
+        fallback: impl FnOnce(&'r mut T) -> R,
+    ) -> <R::Residual as Residual>::WithOutput<&'r mut U>
+    where
+        R : Fallible<Output = &'r mut U>,
+    {
+        match self.found {
+            | Ok(found) => <R::Residual as Residual>::from_output(found),
+            | Err(input) => match Try::branch(fallback(input)) {
+                | Ok(found) => <R::Residual as Residual>::from_output(found),
+                | Err(residual) => Residual::with_output(residual),
+            },
+        }
+    }
+
+    /// Ends the chain: what was found, or else the input, given back.
+    pub
+    fn into_result (self)
+      -> Result<&'r mut U, &'r mut T>
+    {
+        self.found
+    }
+}
@@ -0,0 +1,227 @@
+//! "Get-or-init" helpers for lazily initialized `&mut` state.
+//!
+//! The following, quite pervasive, pattern does not compile without Polonius:
+//!
+/*!  - ```rust ,compile_fail
+    # struct Conn;
+    # fn connect () -> Result<Conn, ()> { Ok(Conn) }
+    enum State { Disconnected, Ready(Conn) }
+
+    struct Client { state: State }
+
+    impl Client {
+        fn conn (&mut self)
+          -> Result<&mut Conn, ()>
+        {
+            if let State::Ready(conn) = &mut self.state {
+                return Ok(conn);
+            }
+            self.state = State::Ready(connect()?);
+            match &mut self.state {
+                | State::Ready(conn) => Ok(conn),
+                | _ => unreachable!(),
+            }
+        }
+    }
+    ``` */
+//!
+//! Hence [`get_or_try_init()`] (and [`get_or_init()`]), as well as the
+//! fallible version of [`Option::get_or_insert_with()`] which the standard
+//! library lacks: [`OptionExt::get_or_try_insert_with()`].
+
+use crate::{
+    Fallible,
+    ForLt,
+    polonius,
+    PoloniusResult,
+    Residual,
+    Try,
+};
+
+/// Returns the part of the `slot` which `project` yields, if it does;
+/// otherwise, (re)initializes the `slot` with `init` before projecting it.
+///
+/// ## Panics
+///
+/// If `project` fails on the freshly initialized `slot`.
+///
+/// ## Example
+///
+/**  - ```rust
+    use ::polonius_the_crab::init::get_or_init;
+
+    enum Cache { Stale(u32), Fresh(Vec<u32>) }
+
+    let mut cache = Cache::Stale(3);
+    let values = get_or_init(
+        &mut cache,
+        |cache| match cache {
+            | Cache::Fresh(values) => Some(values),
+            | Cache::Stale(_) => None,
+        },
+        |cache| match *cache {
+            | Cache::Stale(n) => Cache::Fresh((0 .. n).collect()),
+            | Cache::Fresh(_) => unreachable!(),
+        },
+    );
+    values.push(3);
+    assert!(matches!(cache, Cache::Fresh(ref v) if *v == [0, 1, 2, 3]));
+    ``` */
+pub
+fn get_or_init<S, T : ?Sized> (
+    slot: &'_ mut S,
+    mut project: impl for<'a> FnMut(&'a mut S) -> Option<&'a mut T>,
+    init: impl FnOnce(&mut S) -> S,
+) -> &'_ mut T
+{
+    match lookup(slot, &mut project) {
+        | PoloniusResult::Borrowing(it) => it,
+        | PoloniusResult::Owned { input_borrow: slot, .. } => {
+            *slot = init(slot);
+            project_init(slot, project)
+        },
+    }
+}
+
+/// Fallible version of [`get_or_init()`]: an error (or a [`None`]) returned by
+/// `init` is propagated, and the `slot` is left untouched.
+///
+/// Works with any [`Result`] or [`Option`]-returning `init`.
+///
+/// ## Panics
+///
+/// If `project` fails on the freshly initialized `slot`.
+///
+/// ## Example
+///
+/**  - ```rust
+    use ::polonius_the_crab::init::get_or_try_init;
+
+    struct Conn { /* … */ }
+    fn connect (addr: &str) -> Result<Conn, String> {
+        // …
+        # Ok(Conn {})
+    }
+
+    enum State {
+        Disconnected { addr: String },
+        Ready(Conn),
+    }
+
+    impl State {
+        fn conn (&mut self)
+          -> Result<&mut Conn, String>
+        {
+            get_or_try_init(
+                self,
+                |state| match state {
+                    | State::Ready(conn) => Some(conn),
+                    | _ => None,
+                },
+                |state| match state {
+                    | State::Disconnected { addr } => connect(addr).map(State::Ready),
+                    | State::Ready(_) => unreachable!(),
+                },
+            )
+        }
+    }
+
+    let mut state = State::Disconnected { addr: "localhost".into() };
+    let _conn: &mut Conn = state.conn().unwrap();
+    assert!(matches!(state, State::Ready(_)));
+    ``` */
+pub
+fn get_or_try_init<S, T : ?Sized, R> (
+    slot: &'_ mut S,
+    mut project: impl for<'a> FnMut(&'a mut S) -> Option<&'a mut T>,
+    init: impl FnOnce(&mut S) -> R,
+) -> <R::Residual as Residual>::WithOutput<&'_ mut T>
+where
+    R : Fallible<Output = S>,
+{
+    match lookup(slot, &mut project) {
+        | PoloniusResult::Borrowing(it) => {
+            <R::Residual as Residual>::from_output(it)
+        },
+        | PoloniusResult::Owned { input_borrow: slot, .. } => {
+            match Try::branch(init(slot)) {
+                | Ok(value) => {
+                    *slot = value;
+                    <R::Residual as Residual>::from_output(
+                        project_init(slot, project)
+                    )
+                },
+                | Err(residual) => Residual::with_output(residual),
+            }
+        },
+    }
+}
+
+/// Extension trait for [`Option`].
+pub
+trait OptionExt<T> {
+    /// Fallible version of [`Option::get_or_insert_with()`]: an error (or a
+    /// [`None`]) returned by `f` is propagated, and `self` is left untouched
+    /// (as a [`None`]).
+    ///
+    /// The standard library has an unstable inherent method by the same name,
+    /// which method-call syntax would pick over this one were it to be
+    /// stabilized (hence the `unstable_name_collisions` lint): call this one
+    /// through the trait instead.
+    ///
+    /// ```rust
+    /// use ::polonius_the_crab::init::OptionExt;
+    ///
+    /// let mut port = None::<u16>;
+    /// assert!(OptionExt::get_or_try_insert_with(&mut port, || "http".parse()).is_err());
+    /// assert_eq!(port, None);
+    /// *OptionExt::get_or_try_insert_with(&mut port, || "80".parse()).unwrap() += 8000;
+    /// assert_eq!(OptionExt::get_or_try_insert_with(&mut port, || None), Some(&mut 8080));
+    /// ```
+    fn get_or_try_insert_with<R> (&mut self, f: impl FnOnce() -> R)
+      -> <R::Residual as Residual>::WithOutput<&'_ mut T>
+    where
+        R : Fallible<Output = T>,
+    ;
+}
+
+impl<T> OptionExt<T> for Option<T> {
+    fn get_or_try_insert_with<R> (&mut self, f: impl FnOnce() -> R)
+      -> <R::Residual as Residual>::WithOutput<&'_ mut T>
+    where
+        R : Fallible<Output = T>,
+    {
+        match lookup(self, &mut Option::as_mut) {
+            | PoloniusResult::Borrowing(it) => {
+                <R::Residual as Residual>::from_output(it)
+            },
+            | PoloniusResult::Owned { input_borrow: this, .. } => {
+                match Try::branch(f()) {
+                    | Ok(value) => {
+                        <R::Residual as Residual>::from_output(this.insert(value))
+                    },
+                    | Err(residual) => Residual::with_output(residual),
+                }
+            },
+        }
+    }
+}
+
+fn lookup<'s, S, T : ?Sized> (
+    slot: &'s mut S,
+    project: &mut impl for<'a> FnMut(&'a mut S) -> Option<&'a mut T>,
+) -> PoloniusResult<&'s mut T, (), &'s mut S>
+{
+    polonius::<_, _, ForLt!(<'a> = &'a mut T)>(slot, |slot| match project(slot) {
+        | Some(it) => PoloniusResult::Borrowing(it),
+        | None => PoloniusResult::Owned(()),
+    })
+}
+
+fn project_init<S, T : ?Sized> (
+    slot: &'_ mut S,
+    mut project: impl for<'a> FnMut(&'a mut S) -> Option<&'a mut T>,
+) -> &'_ mut T
+{
+    project(slot).expect("`project` to succeed on the freshly `init`ialized slot")
+}
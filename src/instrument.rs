@@ -0,0 +1,199 @@
+//! Per-call-site counters of the branches taken by [`polonius()`]: the
+//! [`Borrowing`] one (_e.g._, a cache hit), _vs._ the [`Owned`] one (_e.g._,
+//! a cache miss).
+//!
+//! Only available with the `instrument` Cargo feature: without it, nothing
+//! gets recorded (nor compiled).
+//!
+//! [`polonius()`] is `#[track_caller]` under this feature, so the counters are
+//! keyed by the [`Location`] of its caller, _i.e._, by the `file!()` and
+//! `line!()` (and column) of each [`polonius!`] invocation.
+//!
+//!   - A [`polonius_loop!`] records one branch per iteration: the `Owned` ones
+//!     being the `continue`s and non-dependent `break`s;
+//!
+//!   - a [`polonius_first_of!`] records one branch per lookup tried;
+//!
+//!   - calls made by this crate's own helpers (such as [`crate::tree`]) are
+//!     keyed by their location within this crate;
+//!
+//!   - with the `polonius` Cargo feature, the macros no longer call
+//!     [`polonius()`], so they record nothing.
+//!
+//! The registry is a lock-free `static` table of [`CAPACITY`] call sites,
+//! which works in `no_std` environments. Call sites beyond that are not
+//! recorded, but [counted][overflowed()].
+//!
+//! ## Example
+//!
+/*!  - ```rust
+    use {
+        ::polonius_the_crab::{instrument, prelude::*},
+        ::std::collections::HashMap,
+    };
+
+    fn get_or_insert (mut map: &'_ mut HashMap<i32, String>, key: i32)
+      -> &'_ String
+    {
+        polonius!(|map| -> &'polonius String {
+            if let Some(v) = map.get(&key) {
+                polonius_return!(v);
+            }
+        });
+        map.entry(key).or_insert_with(|| key.to_string())
+    }
+
+    let mut map = HashMap::new();
+    for key in [1, 2, 1, 1] {
+        get_or_insert(&mut map, key);
+    }
+    let counters: Vec<_> = instrument::take().collect();
+    assert_eq!(counters.len(), 1);
+    assert_eq!(counters[0].location.file(), file!());
+    assert_eq!((counters[0].hits, counters[0].misses), (2, 2));
+    // `take()` reset them.
+    assert!(instrument::snapshot().all(|c| c.hits + c.misses == 0));
+    ``` */
+//!
+//! [`Borrowing`]: crate::PoloniusResult::Borrowing
+//! [`Owned`]: type@crate::PoloniusResult::Owned
+//! [`polonius()`]: crate::polonius()
+//! [`polonius!`]: crate::polonius!
+//! [`polonius_loop!`]: crate::polonius_loop!
+//! [`polonius_first_of!`]: crate::polonius_first_of!
+
+use ::core::{
+    panic::Location,
+    ptr,
+    sync::atomic::{AtomicPtr, AtomicUsize, Ordering},
+};
+
+/// Maximum number of distinct call sites recorded.
+pub
+const CAPACITY: usize = 256;
+
+/// The counters of a given call site, as returned by [`snapshot()`] or
+/// [`take()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub
+struct Counters {
+    /// The location of the [`polonius()`][crate::polonius()] call.
+    pub location: &'static Location<'static>,
+
+    /// How many times the `Borrowing` branch was taken.
+    pub hits: usize,
+
+    /// How many times the `Owned` branch was taken.
+    pub misses: usize,
+}
+
+struct Slot {
+    /// Null while the slot is free; never changes once claimed.
+    location: AtomicPtr<Location<'static>>,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+impl Slot {
+    fn location (&self)
+      -> Option<&'static Location<'static>>
+    {
+        let ptr = self.location.load(Ordering::Acquire);
+        // SAFETY: only ever set (once) from a `&'static Location<'static>`.
+        #[allow(unsafe_code)]
+        unsafe { ptr.as_ref() }
+    }
+
+    fn counters (
+        &self,
+        read: impl Fn(&AtomicUsize) -> usize,
+    ) -> Option<Counters>
+    {
+        Some(Counters {
+            location: self.location()?,
+            hits: read(&self.hits),
+            misses: read(&self.misses),
+        })
+    }
+}
+
+#[allow(clippy::declare_interior_mutable_const)]
+const FREE: Slot = Slot {
+    location: AtomicPtr::new(ptr::null_mut()),
+    hits: AtomicUsize::new(0),
+    misses: AtomicUsize::new(0),
+};
+
+static REGISTRY: [Slot; CAPACITY] = [FREE; CAPACITY];
+
+static OVERFLOW: AtomicUsize = AtomicUsize::new(0);
+
+/// Called by `polonius()`.
+pub(crate)
+fn record (location: &'static Location<'static>, hit: bool)
+{
+    let Some(slot) = slot_for(location) else {
+        OVERFLOW.fetch_add(1, Ordering::Relaxed);
+        return;
+    };
+    let counter = if hit { &slot.hits } else { &slot.misses };
+    counter.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Open addressing, keyed by the _value_ of the `location` (the same call site
+/// may have several `Location` statics, _e.g._, across generic
+/// instantiations).
+fn slot_for (location: &'static Location<'static>)
+  -> Option<&'static Slot>
+{
+    let hash = (location.line() as usize) ^ ((location.column() as usize) << 16);
+    for i in 0 .. CAPACITY {
+        let slot = &REGISTRY[hash.wrapping_add(i) % CAPACITY];
+        let claimed = match slot.location.compare_exchange(
+            ptr::null_mut(),
+            location as *const _ as *mut _,
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        ) {
+            | Ok(_) => return Some(slot),
+            | Err(_) => slot.location().unwrap(),
+        };
+        if claimed == location {
+            return Some(slot);
+        }
+    }
+    None
+}
+
+/// The current counters of every recorded call site.
+pub
+fn snapshot ()
+  -> impl Iterator<Item = Counters>
+{
+    REGISTRY.iter().filter_map(|slot| slot.counters(|c| c.load(Ordering::Relaxed)))
+}
+
+/// Like [`snapshot()`], but resetting each counter as it is read.
+pub
+fn take ()
+  -> impl Iterator<Item = Counters>
+{
+    REGISTRY.iter().filter_map(|slot| slot.counters(|c| c.swap(0, Ordering::Relaxed)))
+}
+
+/// How many branches went unrecorded, for lack of a free slot among the
+/// [`CAPACITY`] ones.
+pub
+fn overflowed ()
+  -> usize
+{
+    OVERFLOW.load(Ordering::Relaxed)
+}
+
+/// Resets every counter, including the [`overflowed()`] one.
+pub
+fn reset ()
+{
+    take().for_each(drop);
+    OVERFLOW.store(0, Ordering::Relaxed);
+}
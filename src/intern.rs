@@ -0,0 +1,136 @@
+//! An [`Interner`], _i.e._, a deduplicating store of (string) values.
+//!
+//! `intern(&mut self, s: &str) -> &str` is yet another instance of the
+//! conditional-return-of-a-borrow problem:
+//!
+/*!  - ```rust ,compile_fail
+    use ::std::collections::BTreeSet;
+
+    fn intern<'r> (set: &'r mut BTreeSet<String>, s: &str)
+      -> &'r str
+    {
+        if let Some(interned) = set.get(s) {
+            return interned;
+        }
+        set.insert(s.to_owned());
+        set.get(s).unwrap()
+    }
+    ``` */
+
+use {
+    ::alloc::{
+        borrow::ToOwned,
+        collections::BTreeSet,
+    },
+    ::core::borrow::Borrow,
+    crate::{
+        ForLt,
+        polonius,
+        PoloniusResult,
+    },
+};
+
+/// A deduplicating store of `T`s (by default, of [`str`]ings): interning an
+/// already-present value returns a borrow to the stored copy, and only a
+/// new value gets allocated (through [`ToOwned`]).
+///
+/// ## Example
+///
+/**  - ```rust
+    use ::polonius_the_crab::intern::Interner;
+
+    let mut interner = Interner::new();
+    let hello: *const str = interner.intern("hello");
+    interner.intern("world");
+    // Same allocation: `"hello"` was not stored (nor allocated) again.
+    assert!(::core::ptr::eq(hello, interner.intern("hello")));
+    assert_eq!(interner.len(), 2);
+    ``` */
+pub
+struct Interner<T : ?Sized + ToOwned = str> {
+    set: BTreeSet<T::Owned>,
+}
+
+impl<T : ?Sized + ToOwned> Default for Interner<T> {
+    #[inline]
+    fn default ()
+      -> Self
+    {
+        Self { set: BTreeSet::new() }
+    }
+}
+
+impl<T : ?Sized + ToOwned> Interner<T> {
+    /// Creates an empty interner.
+    #[inline]
+    pub
+    const
+    fn new ()
+      -> Self
+    {
+        Self { set: BTreeSet::new() }
+    }
+
+    /// The number of distinct values interned so far.
+    #[inline]
+    pub
+    fn len (&self)
+      -> usize
+    {
+        self.set.len()
+    }
+
+    /// Whether nothing has been interned yet.
+    #[inline]
+    pub
+    fn is_empty (&self)
+      -> bool
+    {
+        self.set.is_empty()
+    }
+
+    /// The interned values, in order.
+    pub
+    fn iter (&self)
+      -> impl '_ + Iterator<Item = &'_ T>
+    {
+        self.set.iter().map(Borrow::borrow)
+    }
+}
+
+impl<T : ?Sized + Ord + ToOwned> Interner<T>
+where
+    T::Owned : Ord,
+{
+    /// Returns the interned copy of `value`, if any.
+    #[inline]
+    pub
+    fn get (&self, value: &'_ T)
+      -> Option<&'_ T>
+    {
+        self.set.get(value).map(Borrow::borrow)
+    }
+
+    /// Returns the interned copy of `value`, interning (and thus allocating)
+    /// it first if need be.
+    pub
+    fn intern (&mut self, value: &'_ T)
+      -> &'_ T
+    {
+        match polonius::<_, _, ForLt!(<'r> = &'r T)>(&mut self.set, |set| {
+            match set.get(value) {
+                | Some(interned) => PoloniusResult::Borrowing(interned.borrow()),
+                | None => PoloniusResult::Owned(()),
+            }
+        })
+        {
+            | PoloniusResult::Borrowing(interned) => interned,
+            | PoloniusResult::Owned { input_borrow: set, .. } => {
+                set.insert(value.to_owned());
+                set.get(value)
+                    .expect("just inserted")
+                    .borrow()
+            },
+        }
+    }
+}
@@ -1,10 +1,23 @@
 #![doc = include_str!("../README.md")]
 #![no_std]
-#![cfg_attr(feature = "polonius",
+#![cfg_attr(all(feature = "polonius", not(feature = "instrument")),
     forbid(unsafe_code),
 )]
+// `instrument` holds a scoped `#[allow(unsafe_code)]`.
+#![cfg_attr(all(feature = "polonius", feature = "instrument"),
+    deny(unsafe_code),
+)]
+#![cfg_attr(feature = "nightly-try",
+    feature(try_trait_v2),
+)]
 #![allow(uncommon_codepoints)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
 /// ACT I SCENE I. Elsinore. A platform before the castle.
 pub
 mod prelude {
@@ -14,9 +27,11 @@ mod prelude {
         polonius_break,
         polonius_break_dependent,
         polonius_continue,
+        polonius_first_of,
         polonius_loop,
         polonius_return,
         polonius_try,
+        PoloniusExt,
     };
 }
 
@@ -26,7 +41,68 @@ pub use ::higher_kinded_types::ForLt;
 pub use macros::ඞ;
 mod macros;
 
+mod assertions;
+
 mod r#try;
+pub use r#try::{Fallible, Residual, Try};
+
+mod r#loop;
+pub use r#loop::{polonius_loop, LoopExit, LoopStep};
+
+mod branch;
+pub use branch::{branch_fn, polonius_with, BranchFn, PoloniusBranch};
+
+mod ext;
+pub use ext::{PoloniusExt, PoloniusGet};
+
+pub
+mod buf;
+
+#[cfg(feature = "alloc")]
+pub
+mod collections;
+
+pub
+mod init;
+
+#[cfg(feature = "instrument")]
+pub
+mod instrument;
+
+#[cfg(feature = "alloc")]
+pub
+mod intern;
+
+#[cfg(feature = "alloc")]
+pub
+mod lru;
+
+pub
+mod map_like;
+
+pub
+mod parse;
+
+pub
+mod path;
+
+#[cfg(feature = "alloc")]
+pub
+mod rc;
+
+#[cfg(feature = "alloc")]
+pub
+mod sorted;
+
+pub
+mod stream;
+
+#[cfg(feature = "std")]
+pub
+mod memo;
+
+pub
+mod tree;
 
 /// The key stone of the API of this crate.
 /// See the [top-level docs][crate] for more info.
@@ -101,6 +177,17 @@ where
 ///         [`polonius!`] invocation, or even to a manual [`polonius()`] call.
 ///
 /// [l]: https://github.com/danielhenrymantilla/polonius-the-crab.rs/issues/11
+///
+/// ## Named branches
+///
+/// The `branch` closure cannot be named, stored, or abstracted over: for that,
+/// see [`PoloniusBranch`] and [`polonius_with()`].
+///
+/// ## Instrumentation
+///
+/// With the `instrument` Cargo feature, the branch taken by each call is
+/// recorded, keyed by the location of the call: see the `instrument` module.
+#[cfg_attr(feature = "instrument", track_caller)]
 pub
 fn polonius<'i, Input : ?Sized, OwnedOutput, BorrowingOutput : ?Sized> (
     input_borrow: &'i mut Input,
@@ -133,17 +220,73 @@ where
     };
     let owned_value = match branch(tentative_borrow) {
         | PoloniusResult::Borrowing(dependent) => {
+            #[cfg(feature = "instrument")]
+            instrument::record(::core::panic::Location::caller(), true);
             return PoloniusResult::Borrowing(dependent);
         },
         | PoloniusResult::Owned { value, .. } => value,
     }; // <- `drop(PoloniusResult::Owned { .. })`.
        // See https://github.com/rust-lang/rust/issues/126520 for more info.
+    #[cfg(feature = "instrument")]
+    instrument::record(::core::panic::Location::caller(), false);
     PoloniusResult::Owned {
         value: owned_value,
         input_borrow,
     }
 }
 
+/// Type-erased flavor of [`polonius()`], taking its `branch` as a
+/// `&mut dyn FnMut`.
+///
+/// [`polonius()`] gets instantiated anew for each (closure) `branch` it is
+/// called with, _i.e._, for each [`polonius!`] call site. This function, on the
+/// other hand, only gets instantiated once per `Input`, `OwnedOutput`, and
+/// `BorrowingOutput` combination, at the cost of a dynamic call.
+///
+/// For crates with many [`polonius!`] call sites, this can reduce compile
+/// times and code size: [`polonius!`] and [`polonius_loop!`] use this function
+/// when prefixed with `dyn`:
+///
+/**  - ```rust
+    use ::polonius_the_crab::prelude::*;
+
+    fn get_or_insert (mut map: &'_ mut ::std::collections::HashMap<i32, String>)
+      -> &'_ String
+    {
+        polonius!(dyn |map| -> &'polonius String {
+            if let Some(v) = map.get(&22) {
+                polonius_return!(v);
+            }
+        });
+        map.insert(22, String::from("…"));
+        &map[&22]
+    }
+    ``` */
+///
+/// Since the `branch` is only borrowed, it has to be `FnMut` (rather than
+/// `FnOnce`): it can no longer move captured variables out.
+#[cfg_attr(feature = "instrument", track_caller)]
+pub
+fn polonius_dyn<'i, 'b, Input : ?Sized, OwnedOutput, BorrowingOutput : ?Sized> (
+    input_borrow: &'i mut Input,
+    branch:
+        &'b mut dyn for<'any>
+            FnMut(&'any mut Input)
+              -> PoloniusResult<
+                    BorrowingOutput::Of<'any>,
+                    OwnedOutput,
+                >
+    ,
+) -> PoloniusResult<
+        BorrowingOutput::Of<'i>,
+        OwnedOutput, &'i mut Input,
+    >
+where
+    BorrowingOutput : 'b + ForLt,
+{
+    polonius::<_, _, BorrowingOutput>(input_borrow, |it| branch(it))
+}
+
 /// Placeholder type to be used when _constructing_ a
 /// [`PoloniusResult::Owned`].
 ///
@@ -0,0 +1,158 @@
+//! Function-level (macro-free) counterpart of [`polonius_loop!`].
+//!
+//! [`polonius_loop!`]: crate::polonius_loop!
+
+use crate::{
+    ForLt,
+    polonius,
+    PoloniusResult,
+    ඞ::Dependent,
+};
+
+/// Output type of the closure given to [`polonius_loop()`]: what to do after
+/// the current iteration.
+pub
+enum LoopStep<Return, Break, BreakOwned> {
+    /// Proceed to the next iteration, with the input borrow given back.
+    Continue,
+
+    /// Exit the loop with a value not borrowing from the input, so that the
+    /// input borrow is given back (see [`LoopExit::BreakOwned`]).
+    BreakOwned(BreakOwned),
+
+    /// Exit the loop with a value borrowing from the input.
+    BreakDependent(Break),
+
+    /// Exit the loop _and the function_ with a value borrowing from the input.
+    ///
+    /// This is no different from [`Self::BreakDependent`] as far as
+    /// [`polonius_loop()`] is concerned; it is just a convenience to
+    /// distinguish these two exits, like [`polonius_return!`] and
+    /// [`polonius_break_dependent!`] do.
+    ///
+    /// [`polonius_return!`]: crate::polonius_return!
+    /// [`polonius_break_dependent!`]: crate::polonius_break_dependent!
+    Return(Return),
+}
+
+/// Output type of [`polonius_loop()`]: how the loop was exited.
+pub
+enum LoopExit<Return, Break, BreakOwned, InputBorrow> {
+    /// The loop was exited through [`LoopStep::BreakOwned`]: the input borrow
+    /// is given back.
+    BreakOwned {
+        /// The inner value of the [`LoopStep::BreakOwned`].
+        value: BreakOwned,
+
+        /// The input borrow, given back.
+        input_borrow: InputBorrow,
+    },
+
+    /// The loop was exited through [`LoopStep::BreakDependent`].
+    BreakDependent(Break),
+
+    /// The loop was exited through [`LoopStep::Return`].
+    Return(Return),
+}
+
+/// Function-level (macro-free) flavor of [`polonius_loop!`]: calls `step` with
+/// the (reborrowed) input until it returns something other than
+/// [`LoopStep::Continue`].
+///
+/// As with [`polonius()`], the dependent types are to be turbofished, as
+/// [`ForLt!`] types: `Return` first, then `Break`. For a loop without
+/// [`LoopStep::BreakDependent`] exits, any type can be used for the latter,
+/// _e.g._, <code>[ForLt!]\(\<\'r\> = [Infallible][::core::convert::Infallible]\)</code>.
+///
+/// ## Example
+///
+/**  - ```rust
+    use ::polonius_the_crab::{
+        ForLt,
+        polonius_loop,
+        LoopExit,
+        LoopStep,
+    };
+
+    /// Returns the first non-empty line of `lines`, if any, or else pushes
+    /// (and returns) an empty one.
+    fn first_non_empty<'i> (lines: &'i mut Vec<String>)
+      -> &'i mut String
+    {
+        let mut i = 0;
+        let exit = polonius_loop::<_, _, ForLt!(<'r> = &'r mut String), ForLt!(<'r> = ::core::convert::Infallible)>(
+            lines,
+            |lines| match lines.get_mut(i) {
+                | Some(line) if !line.is_empty() => LoopStep::Return(line),
+                | Some(_) => {
+                    i += 1;
+                    LoopStep::Continue
+                },
+                | None => LoopStep::BreakOwned(()),
+            },
+        );
+        match exit {
+            | LoopExit::Return(line) => line,
+            | LoopExit::BreakDependent(unreachable) => match unreachable {},
+            | LoopExit::BreakOwned { value: (), input_borrow: lines } => {
+                lines.push(String::new());
+                lines.last_mut().unwrap()
+            },
+        }
+    }
+
+    let mut lines = vec!["".into(), "hello".into()];
+    first_non_empty(&mut lines).push('!');
+    assert_eq!(lines, ["", "hello!"]);
+    ``` */
+///
+/// [`polonius_loop!`]: crate::polonius_loop!
+/// [`ForLt!`]: crate::ForLt!
+/// [ForLt!]: crate::ForLt!
+pub
+fn polonius_loop<'i, Input : ?Sized, BreakOwned, Return : ?Sized, Break : ?Sized> (
+    mut input_borrow: &'i mut Input,
+    mut step:
+        impl for<'any>
+            FnMut(&'any mut Input)
+              -> LoopStep<
+                    Return::Of<'any>,
+                    Break::Of<'any>,
+                    BreakOwned,
+                >
+    ,
+) -> LoopExit<
+        Return::Of<'i>,
+        Break::Of<'i>,
+        BreakOwned, &'i mut Input,
+    >
+where
+    Return : ForLt,
+    Break : ForLt,
+{
+    loop {
+        match polonius::<_, _, ForLt!(<'r> = Dependent<Return::Of<'r>, Break::Of<'r>>)>(
+            input_borrow,
+            |it| match step(it) {
+                | LoopStep::Continue => PoloniusResult::Owned(None),
+                | LoopStep::BreakOwned(value) => PoloniusResult::Owned(Some(value)),
+                | LoopStep::BreakDependent(it) => PoloniusResult::Borrowing(Dependent::Break(it)),
+                | LoopStep::Return(it) => PoloniusResult::Borrowing(Dependent::Return(it)),
+            },
+        )
+        {
+            | PoloniusResult::Borrowing(Dependent::Return(it) | Dependent::Propagate(it)) => {
+                return LoopExit::Return(it);
+            },
+            | PoloniusResult::Borrowing(Dependent::Break(it)) => {
+                return LoopExit::BreakDependent(it);
+            },
+            | PoloniusResult::Owned { value: Some(value), input_borrow } => {
+                return LoopExit::BreakOwned { value, input_borrow };
+            },
+            | PoloniusResult::Owned { value: None, input_borrow: it } => {
+                input_borrow = it;
+            },
+        }
+    }
+}
@@ -0,0 +1,396 @@
+//! A least-recently-used cache, whose lookups promote the entry _and_ return
+//! a borrow to it.
+//!
+//! "Find the entry, bump it, and return `&mut V`; otherwise evict and insert"
+//! does not compile without Polonius, even with a promoting `get_mut()`:
+//!
+/*!  - ```rust ,compile_fail
+    use ::polonius_the_crab::lru::LruCache;
+
+    fn get_or_insert_with<'r> (
+        cache: &'r mut LruCache<u32, String>,
+        key: u32,
+        make_value: impl FnOnce() -> String,
+    ) -> &'r mut String
+    {
+        if let Some(value) = cache.get_mut(&key) {
+            return value;
+        }
+        cache.insert(key, make_value());
+        cache.get_mut(&key).unwrap()
+    }
+    ``` */
+
+use {
+    ::alloc::{
+        collections::BTreeMap,
+        vec::Vec,
+    },
+    ::core::borrow::Borrow,
+    crate::{
+        Fallible,
+        ForLt,
+        polonius,
+        PoloniusResult,
+        Residual,
+        Try,
+    },
+};
+
+/// The "null pointer" of the intrusive linked list of entries.
+const NIL: usize = usize::MAX;
+
+struct Entry<K, V> {
+    key: K,
+    value: V,
+    /// The more recently used neighbor.
+    prev: usize,
+    /// The less recently used neighbor.
+    next: usize,
+}
+
+/// A cache holding at most `capacity` entries, evicting the least recently
+/// used one to make room for new ones.
+///
+/// Both looking an entry up (through `&mut self` methods) and (re)inserting
+/// it count as using it.
+///
+/// ## Example
+///
+/**  - ```rust
+    use ::polonius_the_crab::lru::LruCache;
+
+    let mut cache = LruCache::new(2);
+    cache.get_or_insert_with("a", || 1);
+    cache.get_or_insert_with("b", || 2);
+    // Use `a`: `b` becomes the least recently used entry…
+    *cache.get_or_insert_with("a", || unreachable!()) += 10;
+    // … and thus the one evicted.
+    assert_eq!(cache.insert("c", 3), Some(("b", 2)));
+    assert_eq!(cache.peek(&"a"), Some(&11));
+    ``` */
+pub
+struct LruCache<K, V> {
+    index: BTreeMap<K, usize>,
+    entries: Vec<Entry<K, V>>,
+    /// The most recently used entry.
+    head: usize,
+    /// The least recently used entry.
+    tail: usize,
+    capacity: usize,
+}
+
+impl<K, V> LruCache<K, V> {
+    /// Creates an empty cache, holding at most `capacity` entries.
+    ///
+    /// ## Panics
+    ///
+    /// If `capacity` is zero.
+    pub
+    fn new (capacity: usize)
+      -> Self
+    {
+        assert!(capacity > 0, "`capacity` must not be zero");
+        Self {
+            index: BTreeMap::new(),
+            entries: Vec::new(),
+            head: NIL,
+            tail: NIL,
+            capacity,
+        }
+    }
+
+    /// The maximum number of entries.
+    #[inline]
+    pub
+    fn capacity (&self)
+      -> usize
+    {
+        self.capacity
+    }
+
+    /// The current number of entries.
+    #[inline]
+    pub
+    fn len (&self)
+      -> usize
+    {
+        self.entries.len()
+    }
+
+    /// Whether the cache is empty.
+    #[inline]
+    pub
+    fn is_empty (&self)
+      -> bool
+    {
+        self.entries.is_empty()
+    }
+
+    /// The entries, from the most recently used to the least recently used.
+    pub
+    fn iter (&self)
+      -> impl '_ + Iterator<Item = (&'_ K, &'_ V)>
+    {
+        let mut cursor = self.head;
+        ::core::iter::from_fn(move || {
+            let entry = self.entries.get(cursor)?;
+            cursor = entry.next;
+            Some((&entry.key, &entry.value))
+        })
+    }
+
+    fn unlink (&mut self, idx: usize)
+    {
+        let Entry { prev, next, .. } = self.entries[idx];
+        match prev {
+            | NIL => self.head = next,
+            | _ => self.entries[prev].next = next,
+        }
+        match next {
+            | NIL => self.tail = prev,
+            | _ => self.entries[next].prev = prev,
+        }
+    }
+
+    fn link_front (&mut self, idx: usize)
+    {
+        let head = self.head;
+        self.entries[idx].prev = NIL;
+        self.entries[idx].next = head;
+        match head {
+            | NIL => self.tail = idx,
+            | _ => self.entries[head].prev = idx,
+        }
+        self.head = idx;
+    }
+
+    fn promote (&mut self, idx: usize)
+    {
+        if self.head != idx {
+            self.unlink(idx);
+            self.link_front(idx);
+        }
+    }
+}
+
+impl<K : Ord + Clone, V> LruCache<K, V> {
+    /// Returns the value for `key`, if any, **without** promoting it.
+    pub
+    fn peek<Q> (&self, key: &'_ Q)
+      -> Option<&'_ V>
+    where
+        K : Borrow<Q>,
+        Q : ?Sized + Ord,
+    {
+        let &idx = self.index.get(key)?;
+        Some(&self.entries[idx].value)
+    }
+
+    /// Whether there is an entry for `key` (which does **not** promote it).
+    #[inline]
+    pub
+    fn contains_key<Q> (&self, key: &'_ Q)
+      -> bool
+    where
+        K : Borrow<Q>,
+        Q : ?Sized + Ord,
+    {
+        self.index.contains_key(key)
+    }
+
+    /// Returns the value for `key`, if any, promoting it to most recently
+    /// used.
+    pub
+    fn get_mut<Q> (&mut self, key: &'_ Q)
+      -> Option<&'_ mut V>
+    where
+        K : Borrow<Q>,
+        Q : ?Sized + Ord,
+    {
+        let &idx = self.index.get(key)?;
+        self.promote(idx);
+        Some(&mut self.entries[idx].value)
+    }
+
+    /// Inserts (or replaces) the entry for `key`, as the most recently used.
+    ///
+    /// Returns the replaced entry, if `key` was already present, or else the
+    /// evicted one, if the cache was full.
+    pub
+    fn insert (&mut self, key: K, value: V)
+      -> Option<(K, V)>
+    {
+        if let Some(&idx) = self.index.get(&key) {
+            self.promote(idx);
+            let prev = ::core::mem::replace(&mut self.entries[idx].value, value);
+            return Some((key, prev));
+        }
+        let evicted = self.make_room();
+        self.insert_new(key, value);
+        evicted
+    }
+
+    /// Removes and returns the least recently used entry.
+    pub
+    fn pop_lru (&mut self)
+      -> Option<(K, V)>
+    {
+        match self.tail {
+            | NIL => None,
+            | tail => Some(self.remove_at(tail)),
+        }
+    }
+
+    /// Returns the value for `key`, promoting it to most recently used, or
+    /// else inserts `make_value()` (evicting the least recently used entry if
+    /// the cache is full).
+    pub
+    fn get_or_insert_with (
+        &mut self,
+        key: K,
+        make_value: impl FnOnce() -> V,
+    ) -> &'_ mut V
+    {
+        match self.lookup(&key) {
+            | PoloniusResult::Borrowing(value) => value,
+            | PoloniusResult::Owned { input_borrow: this, .. } => {
+                let value = make_value();
+                this.make_room();
+                this.insert_new(key, value)
+            },
+        }
+    }
+
+    /// Fallible version of [`Self::get_or_insert_with()`]: an error (or a
+    /// [`None`]) returned by `make_value` is propagated, and the cache is
+    /// left untouched.
+    ///
+    /// ```rust
+    /// use ::polonius_the_crab::lru::LruCache;
+    ///
+    /// let mut cache = LruCache::<&str, i32>::new(1);
+    /// cache.insert("one", 1);
+    /// assert!(cache.get_or_try_insert_with("two", || "2?".parse()).is_err());
+    /// // Nothing got evicted.
+    /// assert_eq!(cache.peek("one"), Some(&1));
+    /// ```
+    pub
+    fn get_or_try_insert_with<R> (
+        &mut self,
+        key: K,
+        make_value: impl FnOnce() -> R,
+    ) -> <R::Residual as Residual>::WithOutput<&'_ mut V>
+    where
+        R : Fallible<Output = V>,
+    {
+        match self.lookup(&key) {
+            | PoloniusResult::Borrowing(value) => {
+                <R::Residual as Residual>::from_output(value)
+            },
+            | PoloniusResult::Owned { input_borrow: this, .. } => {
+                match Try::branch(make_value()) {
+                    | Ok(value) => {
+                        this.make_room();
+                        <R::Residual as Residual>::from_output(
+                            this.insert_new(key, value)
+                        )
+                    },
+                    | Err(residual) => Residual::with_output(residual),
+                }
+            },
+        }
+    }
+
+    fn lookup (&mut self, key: &'_ K)
+      -> PoloniusResult<&'_ mut V, (), &'_ mut Self>
+    {
+        polonius::<_, _, ForLt!(<'r> = &'r mut V)>(self, |this| {
+            match this.get_mut(key) {
+                | Some(value) => PoloniusResult::Borrowing(value),
+                | None => PoloniusResult::Owned(()),
+            }
+        })
+    }
+
+    /// Evicts the least recently used entry, if full.
+    fn make_room (&mut self)
+      -> Option<(K, V)>
+    {
+        if self.len() < self.capacity {
+            return None;
+        }
+        self.pop_lru()
+    }
+
+    /// Inserts an entry for a (known to be) absent `key`, as the most
+    /// recently used one.
+    fn insert_new (&mut self, key: K, value: V)
+      -> &'_ mut V
+    {
+        let idx = self.entries.len();
+        self.index.insert(key.clone(), idx);
+        self.entries.push(Entry { key, value, prev: NIL, next: NIL });
+        self.link_front(idx);
+        &mut self.entries[idx].value
+    }
+
+    fn remove_at (&mut self, idx: usize)
+      -> (K, V)
+    {
+        self.unlink(idx);
+        let Entry { key, value, .. } = self.entries.swap_remove(idx);
+        self.index.remove(&key);
+        // Fix the links to the entry moved from the end into `idx`.
+        if idx < self.entries.len() {
+            let Entry { prev, next, .. } = self.entries[idx];
+            match prev {
+                | NIL => self.head = idx,
+                | _ => self.entries[prev].next = idx,
+            }
+            match next {
+                | NIL => self.tail = idx,
+                | _ => self.entries[next].prev = idx,
+            }
+            let moved_key = &self.entries[idx].key;
+            *self.index.get_mut(moved_key).expect("consistent index") = idx;
+        }
+        (key, value)
+    }
+}
+
+/// Promotes the looked up entries, like the inherent methods do.
+impl<K : Ord + Clone, V> crate::map_like::MapLike for LruCache<K, V> {
+    type Key = K;
+    type Value = V;
+
+    #[inline]
+    fn get_mut (&mut self, key: &'_ K)
+      -> Option<&'_ mut V>
+    {
+        Self::get_mut(self, key)
+    }
+
+    #[inline]
+    fn insert (&mut self, key: K, value: V)
+    {
+        Self::insert(self, key, value);
+    }
+
+    fn insert_and_get_mut (&mut self, key: K, value: V)
+      -> &'_ mut V
+    {
+        match self.index.get(&key) {
+            | Some(&idx) => {
+                self.promote(idx);
+                let slot = &mut self.entries[idx].value;
+                *slot = value;
+                slot
+            },
+            | None => {
+                self.make_room();
+                self.insert_new(key, value)
+            },
+        }
+    }
+}
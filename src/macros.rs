@@ -5,6 +5,7 @@ use super::{
     polonius_break,
     polonius_break_dependent,
     polonius_continue,
+    polonius_first_of,
     polonius_loop,
     polonius_return,
     polonius_try,
@@ -73,42 +74,112 @@ use super::{
         &map[key]
     }
     ``` */
-#[macro_export]
-macro_rules! polonius {(
-    |$var:ident $(,)?| -> $Ret:ty
-        $body:block
-    $(,)?
-) => (
-    match
-        $crate::polonius::<
-            _,
-            _,
-            $crate::ForLt!(<'polonius> = $crate::ඞ::Dependent<$Ret>),
-        >(
-            $var,
-            |mut $var: &mut _| {
-                // silence the unused `mut` warning.
-                #[allow(clippy::self_assignment)] {
-                    $var = $var;
-                }
-                $crate::PoloniusResult::Owned(
-                    if true
-                        $body
-                    else {
-                        // avoid a dead-code warning
-                        $crate::ඞ::None.unwrap()
-                    }
-                )
-            },
-        )
+///
+/// ### `dyn` flavor
+///
+/// `polonius!(dyn |var| -> … { … })` (and `polonius_loop!(dyn |var| -> … { … })`)
+/// use [`polonius_dyn()`] rather than [`polonius()`], to avoid instantiating
+/// the latter anew at each call site. The body then behaves as an `FnMut`
+/// closure: it cannot move captured variables out.
+///
+/// [`polonius()`]: crate::polonius()
+/// [`polonius_dyn()`]: crate::polonius_dyn()
+///
+/// ### Labeled-block flavor
+///
+/// `polonius!('label, |var| -> … { … })` makes [`polonius_return!`] `break`
+/// out of the enclosing `'label: { … }` block (with the dependent value),
+/// rather than `return` from the function. This way, the dependent value can
+/// be used further within the same function, without having to extract a
+/// helper function:
+///
+/**  - ```rust
+    use {
+        ::polonius_the_crab::prelude::*,
+        ::std::collections::HashMap,
+    };
+
+    fn greet (mut names: &'_ mut HashMap<u32, String>, id: u32)
+      -> usize
     {
-        | $crate::PoloniusResult::Borrowing(ret) => return ret.return_no_break(),
-        | $crate::PoloniusResult::Owned { value, input_borrow, .. } => {
-            $var = input_borrow;
-            value
-        },
+        let name: &String = 'found: {
+            polonius!('found, |names| -> &'polonius String {
+                if let Some(name) = names.get(&id) {
+                    polonius_return!(name);
+                }
+            });
+            names.entry(id).or_insert_with(|| format!("user #{id}"))
+        };
+        println!("Hello, {name}!");
+        name.len()
     }
-)}
+
+    let mut names = HashMap::from([(0, "root".to_owned())]);
+    assert_eq!(greet(&mut names, 0), 4);
+    assert_eq!(greet(&mut names, 42), 8);
+    ``` */
+///
+/// Note that the dependent value keeps `var` borrowed for as long as `var`'s
+/// own lifetime. When `var` is a function parameter and is to be used again
+/// after the labeled block, shadow it with a reborrow local to the block
+/// (`let mut var = &mut *var;`) before the `polonius!` invocation.
+///
+/// `polonius!('label, dyn |var| -> … { … })` is also supported.
+///
+/// With the `nightly-try` Cargo feature, a plain `?` within a labeled body
+/// `return`s from the function (as a `?` does), whereas [`polonius_try!`]
+/// `break`s out of `'label` (as [`polonius_return!`] does).
+///
+/// ### With the `polonius` Cargo feature
+///
+/// When the `polonius` feature is enabled (to be used with `-Zpolonius`), this
+/// macro and its companions no longer involve closures, [`ForLt!`] types nor
+/// [`PoloniusResult`]s: they expand to the straight-line code that native
+/// Polonius accepts (`if let … { return … }`, `break`, `continue`). These
+/// `break`s and `continue`s target a labeled `loop` of the expansion, so
+/// that the body's own loops do not change their meaning.
+///
+/// This makes it possible to compile the very same code in both modes, _e.g._,
+/// to compare them before migrating away from this crate.
+///
+/// [`ForLt!`]: crate::ForLt!
+/// [`PoloniusResult`]: crate::PoloniusResult
+#[macro_export]
+macro_rules! polonius {
+    (
+        |$var:ident $(,)?| -> $($Ret_and_body:tt)*
+    ) => (
+        // Splits `$Ret` from `$body` so as to check that `$Ret` does mention
+        // `'polonius`, before forwarding to `ඞ::polonius_unchecked!`.
+        $crate::ඞ::polonius_split_ret! {
+            [polonius] [] $var [] $($Ret_and_body)*
+        }
+    );
+
+    (
+        dyn |$var:ident $(,)?| -> $($Ret_and_body:tt)*
+    ) => (
+        $crate::ඞ::polonius_split_ret! {
+            [polonius_dyn &mut] [] $var [] $($Ret_and_body)*
+        }
+    );
+
+    (
+        $label:lifetime, |$var:ident $(,)?| -> $($Ret_and_body:tt)*
+    ) => (
+        $crate::ඞ::polonius_split_ret! {
+            [polonius] [$label] $var [] $($Ret_and_body)*
+        }
+    );
+
+    (
+        $label:lifetime, dyn |$var:ident $(,)?| -> $($Ret_and_body:tt)*
+    ) => (
+        $crate::ඞ::polonius_split_ret! {
+            [polonius_dyn &mut] [$label] $var [] $($Ret_and_body)*
+        }
+    );
+}
 
 impl<T> ඞ::Dependent<T> {
     pub
@@ -116,7 +187,7 @@ impl<T> ඞ::Dependent<T> {
       -> T
     {
         match self {
-            | Self::Return(it) => it,
+            | Self::Return(it) | Self::Propagate(it) => it,
             | Self::Break(unreachable) => match unreachable {},
         }
     }
@@ -124,22 +195,41 @@ impl<T> ඞ::Dependent<T> {
 
 /// See [`polonius!`] for more info.
 #[macro_export]
-macro_rules! polonius_return {( $e:expr $(,)? ) => (
-    return $crate::PoloniusResult::Borrowing($crate::ඞ::Dependent::Return($e))
-)}
+macro_rules! polonius_return {( $e:expr $(,)? ) => ({
+    let _: cannot_use_this_macro_outside_of__poloniusǃ__or__polonius_loopǃ = ();
+    $crate::ඞ::if_polonius! {
+        native {
+            ඞ_native_return! { $e }
+        }
+        else {
+            return $crate::PoloniusResult::Borrowing($crate::ඞ::Dependent::Return($e))
+        }
+    }
+})}
 
 /// See [`polonius!`] for more info.
 #[macro_export]
-macro_rules! exit_polonius {( $($e:expr $(,)?)? ) => (
-    return $crate::PoloniusResult::Owned(
-        ($($e ,)? (),).0
-    )
-)}
+macro_rules! exit_polonius {( $($e:expr $(,)?)? ) => ({
+    let _: cannot_use_this_macro_outside_of__poloniusǃ = ();
+    $crate::ඞ::if_polonius! {
+        native {
+            ඞ_native_break! { ($($e ,)? (),).0 }
+        }
+        else {
+            return $crate::PoloniusResult::Owned(
+                ($($e ,)? (),).0
+            )
+        }
+    }
+})}
 
 /// Perform the `?` operation inside a [`polonius!`] or [`polonius_loop!`] block.
 ///
 ///   - Only [`Result`] and [`Option`] are supported (_e.g._, no `ControlFlow`).
 ///
+///   - With the `nightly-try` Cargo feature (requires a nightly toolchain), a
+///     plain `?` does the same.
+///
 /// See [`polonius!`] for more info.
 ///
 /// ## Example
@@ -186,6 +276,105 @@ macro_rules! polonius_try {( $e:expr $(,)? ) => (
     }
 )}
 
+/// Tries several lookups in turn, evaluating to the first hit, or else to the
+/// `else` block, which is given the input borrow back.
+///
+/// Each lookup is a closure-like `|binding| expr`, with `expr` evaluating to an
+/// [`Option`] of the (borrowing) hit. By default, the hits are expected to be
+/// `&mut` borrows (as with `.get_mut()`); for other types, annotate the
+/// `else` "closure" with `-> Ret<'polonius>` (as with [`polonius!`]).
+///
+/// Expands to a chain of [`polonius()`] calls or, with the `polonius` Cargo
+/// feature, of `if let Some(hit) = … { break … }`s.
+///
+/// ## Example
+///
+/**  - ```rust
+    use {
+        ::polonius_the_crab::prelude::*,
+        ::std::collections::HashMap,
+    };
+
+    fn lookup<'map> (
+        map: &'map mut HashMap<String, i32>,
+        primary: &str,
+        alias: &str,
+    ) -> &'map mut i32
+    {
+        polonius_first_of!(map => [
+            |m| m.get_mut(primary),
+            |m| m.get_mut(alias),
+            |m| m.get_mut("default"),
+        ] else |m| {
+            m.entry(primary.to_owned()).or_insert(0)
+        })
+    }
+
+    fn name<'map> (map: &'map mut HashMap<u8, String>, id: u8)
+      -> &'map str
+    {
+        polonius_first_of!(map => [
+            |m| m.get(&id).map(String::as_str),
+            |m| m.get(&0).map(String::as_str),
+        ] else |m| -> &'polonius str {
+            m.insert(0, "anonymous".into());
+            &m[&0]
+        })
+    }
+
+    let mut map = HashMap::from([("b".to_owned(), 2)]);
+    *lookup(&mut map, "a", "b") += 40;
+    assert_eq!(map["b"], 42);
+    *lookup(&mut map, "a", "c") += 1;
+    assert_eq!(map["a"], 1);
+
+    let mut names = HashMap::new();
+    assert_eq!(name(&mut names, 42), "anonymous");
+    ``` */
+///
+/// [`polonius()`]: crate::polonius()
+#[macro_export]
+macro_rules! polonius_first_of {
+    (
+        $input:expr => [
+            $( |$binding:ident| $lookup:expr ),+ $(,)?
+        ] else |$else_binding:ident| -> $Ret:ty $else:block $(,)?
+    ) => ({
+        let input_borrow: &mut _ = $input;
+        $crate::ඞ::if_polonius! {
+            native {
+                'polonius_first_of: {
+                    $({
+                        let $binding: &mut _ = &mut *input_borrow;
+                        if let $crate::ඞ::Some(hit) = $lookup {
+                            break 'polonius_first_of hit;
+                        }
+                    })+
+                    let $else_binding = input_borrow;
+                    $else
+                }
+            }
+            else {
+                $crate::ඞ::polonius_first_of_chain! {
+                    input_borrow, $Ret, [$( |$binding| $lookup ),+] |$else_binding| $else
+                }
+            }
+        }
+    });
+
+    (
+        $input:expr => [
+            $( |$binding:ident| $lookup:expr ),+ $(,)?
+        ] else |$else_binding:ident| $else:block $(,)?
+    ) => (
+        $crate::polonius_first_of! {
+            $input => [
+                $( |$binding| $lookup ),+
+            ] else |$else_binding| -> &'polonius mut _ $else
+        }
+    );
+}
+
 /// Convenience support for the `loop { … polonius!(…) }` pattern.
 ///
 /// ### Example
@@ -410,61 +599,23 @@ macro_rules! polonius_try {( $e:expr $(,)? ) => (
 /// have no other choice but to refactor your loop into a smaller helper
 /// function so as to replace that `break` with a `return`.
 #[macro_export]
-macro_rules! polonius_loop {(
-    | $var:ident $(,)? | -> $Ret:ty $(, break: $Break:ty)?
-        $body:block
-    $(,)?
-) => (
-    loop {
-        match
-            $crate::polonius::<
-                _,
-                _,
-                $crate::ForLt!(<'polonius>
-                    = $crate::ඞ::Dependent< $Ret $(, $Break)? >
-                ),
-            >(
-                &mut *$var,
-                |mut $var: &mut _| {
-                    // silence the unused `mut` warning.
-                    #[allow(clippy::self_assignment)] {
-                        $var = $var;
-                    }
-                    let () =
-                        if true
-                            $body
-                        else {
-                            // avoid a dead-code warning
-                            $crate::ඞ::core::option::Option::None.unwrap()
-                        }
-                    ;
-                    $crate::polonius_continue!();
-                },
-            )
-        {
-            | $crate::PoloniusResult::Borrowing(dependent) => match dependent {
-                | $crate::ඞ::Dependent::Return(return_value) => return return_value,
-                | $crate::ඞ::Dependent::Break(break_value) => $crate::ඞ::first! {
-                    $((
-                        break if false { loop {} } else { break_value }
-                    ) (if $Break type else))? ({
-                        let _: $crate::ඞ::dependent_break_without_break_ty_annotation = break_value;
-                        match break_value {}
-                    })
-                },
-            },
-            | $crate::PoloniusResult::Owned { value, input_borrow, .. } => {
-                $var = input_borrow;
-                match value {
-                    | $crate::ඞ::core::ops::ControlFlow::Break(value) => {
-                        break if false { loop {} } else { value };
-                    },
-                    | $crate::ඞ::core::ops::ControlFlow::Continue(()) => continue,
-                }
-            },
+macro_rules! polonius_loop {
+    (
+        | $var:ident $(,)? | -> $($rest:tt)*
+    ) => (
+        $crate::ඞ::polonius_loop_unchecked! {
+            [polonius] |$var| -> $($rest)*
         }
-    }
-)}
+    );
+
+    (
+        dyn | $var:ident $(,)? | -> $($rest:tt)*
+    ) => (
+        $crate::ඞ::polonius_loop_unchecked! {
+            [polonius_dyn &mut] |$var| -> $($rest)*
+        }
+    );
+}
 
 /// `break` a **non-dependent value** out of a [`polonius_loop!`].
 ///
@@ -506,13 +657,21 @@ macro_rules! polonius_loop {(
     ``` */
 ///
 #[macro_export]
-macro_rules! polonius_break {( $($e:expr $(,)?)? ) => (
-    return $crate::PoloniusResult::Owned(
-        $crate::ඞ::core::ops::ControlFlow::Break(
-            ($($e ,)? () ,).0
-        )
-    )
-)}
+macro_rules! polonius_break {( $($e:expr $(,)?)? ) => ({
+    let _: cannot_use_this_macro_outside_of__polonius_loopǃ = ();
+    $crate::ඞ::if_polonius! {
+        native {
+            ඞ_native_break! { ($($e ,)? () ,).0 }
+        }
+        else {
+            return $crate::PoloniusResult::Owned(
+                $crate::ඞ::core::ops::ControlFlow::Break(
+                    ($($e ,)? () ,).0
+                )
+            )
+        }
+    }
+})}
 
 /// `break` a **dependent value** out of a [`polonius_loop!`].
 ///
@@ -643,21 +802,48 @@ macro_rules! polonius_break {( $($e:expr $(,)?)? ) => (
 /// lifetime requirements (those stemming from an actually-dependent break
 /// value).
 #[macro_export]
-macro_rules! polonius_break_dependent {( $e:expr $(,)? ) => (
-    return $crate::PoloniusResult::Borrowing(
-        $crate::ඞ::Dependent::Break($e)
-    )
-)}
+macro_rules! polonius_break_dependent {( $e:expr $(,)? ) => ({
+    let _: cannot_use_this_macro_outside_of__polonius_loopǃ = ();
+    $crate::ඞ::if_polonius! {
+        native {
+            ඞ_native_break! { $e }
+        }
+        else {
+            return $crate::PoloniusResult::Borrowing(
+                $crate::ඞ::Dependent::Break($e)
+            )
+        }
+    }
+})}
 
 /// `continue` to the next iteration of a [`polonius_loop!`].
 #[macro_export]
-macro_rules! polonius_continue {() => (
-    return $crate::PoloniusResult::Owned(
-        $crate::ඞ::core::ops::ControlFlow::<_>::Continue(())
-    )
-)}
+macro_rules! polonius_continue {() => ({
+    let _: cannot_use_this_macro_outside_of__polonius_loopǃ = ();
+    $crate::ඞ::if_polonius! {
+        native {
+            ඞ_native_continue! {}
+        }
+        else {
+            return $crate::PoloniusResult::Owned(
+                $crate::ඞ::core::ops::ControlFlow::<_>::Continue(())
+            )
+        }
+    }
+})}
 
 // macro internals
+//
+// The caller-provided tokens (`$body`, `$e`, …) are interpreted with the
+// caller's edition. The expansions do not depend on the 2021 tail expression
+// temporary scopes nor `if let` scrutinee scopes, and the diverging bodies
+// do not depend on the never type falling back to `()` (thanks to the `ǃ`
+// impl below): the edition 2024 crate at `tests/edition-2024` guards against
+// regressions.
+//
+// The `expr` fragments are kept as is (rather than `expr_2021`, which would
+// break the MSRV): this crate being on edition 2021, they do not match
+// `const { … }` blocks nor `_`, whatever the caller's edition.
 #[doc(hidden)] /** Not part of the public API */ pub
 mod ඞ {
     #![allow(nonstandard_style)]
@@ -671,12 +857,17 @@ mod ඞ {
     {}
 
     pub
-    enum Dependent<Return, Break = Never> {
+    enum Dependent<Return, Break = Never, Propagated = Return> {
         Return(Return),
         Break(Break),
+        /// The residual of a `?` (with the `nightly-try` feature), already
+        /// converted into the return type of the function: it only differs
+        /// from `Return` within a labeled body, out of which it is `return`ed
+        /// rather than `break`ed, as a native `?` would.
+        Propagate(Propagated),
     }
 
-    use {
+    pub use {
         cannot_use__polonius_break_dependentǃ__without_a_break_type_annotation_on__polonius_loopǃ
         as
         Never,
@@ -687,6 +878,425 @@ mod ඞ {
         cannot_use__polonius_break_dependentǃ__without_a_break_type_annotation_on__polonius_loopǃ
     ;
 
+    pub
+    enum cannot_use__polonius_breakǃ__nor__polonius_continueǃ__inside__poloniusǃ__which_is_not_a_loop
+    {}
+
+    pub
+    enum cannot_use__exit_poloniusǃ__inside__polonius_loopǃ__use__polonius_breakǃ__instead
+    {}
+
+    // The macros usable within a `polonius!` or `polonius_loop!` body check
+    // that they are, by naming one of these, which the bodies glob-import.
+    // When not found, or when aliased to one of the empty `enum`s above, the
+    // resulting error message names the misuse.
+    pub
+    mod polonius_scope {
+        pub type cannot_use_this_macro_outside_of__poloniusǃ__or__polonius_loopǃ = ();
+        pub type cannot_use_this_macro_outside_of__poloniusǃ = ();
+        pub type cannot_use_this_macro_outside_of__polonius_loopǃ =
+            super::cannot_use__polonius_breakǃ__nor__polonius_continueǃ__inside__poloniusǃ__which_is_not_a_loop
+        ;
+    }
+
+    pub
+    mod polonius_loop_scope {
+        pub type cannot_use_this_macro_outside_of__poloniusǃ__or__polonius_loopǃ = ();
+        pub type cannot_use_this_macro_outside_of__poloniusǃ =
+            super::cannot_use__exit_poloniusǃ__inside__polonius_loopǃ__use__polonius_breakǃ__instead
+        ;
+        pub type cannot_use_this_macro_outside_of__polonius_loopǃ = ();
+    }
+
+    // With the `polonius` Cargo feature, the body's macros expand to
+    // `ඞ_native_{return,break,continue}!`, which the native expansions define
+    // locally: since labels are hygienic, this is how a `break` within the
+    // body gets to target the `loop` of the expansion (rather than one of the
+    // body's own loops), or the caller's `'label` of a labeled `polonius!`.
+    //
+    // The innermost definitions shadow the outer ones, as with the glob
+    // imports above.
+    #[doc(hidden)] /** Not part of the public API */ #[macro_export]
+    macro_rules! ඞ_native_scope {(
+        return: [$($return:tt)*],
+        break: $break:lifetime $(,)?
+    ) => (
+        #[allow(unused_macros)]
+        macro_rules! ඞ_native_return {( $e:expr ) => (
+            $($return)* $e
+        )}
+
+        #[allow(unused_macros)]
+        macro_rules! ඞ_native_break {( $e:expr ) => (
+            break $break $e
+        )}
+
+        #[allow(unused_macros)]
+        macro_rules! ඞ_native_continue {() => (
+            continue $break
+        )}
+    )} pub use ඞ_native_scope as native_scope;
+
+    pub
+    trait cannot_return_a_value_out_of__polonius_loopǃ__use__polonius_breakǃ__instead
+    {}
+
+    impl cannot_return_a_value_out_of__polonius_loopǃ__use__polonius_breakǃ__instead
+    for
+        ()
+    {}
+
+    // So that diverging bodies keep compiling, whatever the never type
+    // fallback.
+    impl cannot_return_a_value_out_of__polonius_loopǃ__use__polonius_breakǃ__instead
+    for
+        crate::r#try::ǃ
+    {}
+
+    #[inline]
+    pub
+    fn polonius_loop_body<T> (_: T)
+    where
+        T : cannot_return_a_value_out_of__polonius_loopǃ__use__polonius_breakǃ__instead,
+    {}
+
+    // A return type which does not mention `'polonius` cannot borrow from the
+    // input, so it is likely that `'polonius` was forgotten (_e.g._, `-> &V`).
+    // Since such a type may still be intended, this is a (`deprecated`)
+    // warning, rather than an error.
+    #[deprecated(note = "\
+        the return type of `polonius!` does not mention the `'polonius` lifetime \
+        (_e.g._, `-> &'polonius V`), so it cannot borrow from the input\
+    ")]
+    pub
+    const return_type_not_mentioning_polonius: () = ();
+
+    #[doc(hidden)] /** Not part of the public API */ #[macro_export]
+    macro_rules! ඞ_polonius_split_ret {
+        (
+            $entry:tt $label:tt $var:ident [$($Ret:tt)*] $body:block $(,)?
+        ) => (
+            $crate::ඞ::lint_unless_mentions_polonius_lifetime! {
+                [$($Ret)*]
+                $crate::ඞ::polonius_unchecked! {
+                    $entry $label |$var| -> $($Ret)* $body
+                }
+            }
+        );
+
+        (
+            $entry:tt $label:tt $var:ident [$($Ret:tt)*] $tt:tt $($rest:tt)*
+        ) => (
+            $crate::ඞ::polonius_split_ret! {
+                $entry $label $var [$($Ret)* $tt] $($rest)*
+            }
+        );
+    } pub use ඞ_polonius_split_ret as polonius_split_ret;
+
+    #[doc(hidden)] /** Not part of the public API */ #[macro_export]
+    macro_rules! ඞ_lint_unless_mentions_polonius_lifetime {
+        (
+            ['polonius $($skipped:tt)*]
+            $($then:tt)*
+        ) => (
+            $($then)*
+        );
+
+        (
+            [( $($inner:tt)* ) $($rest:tt)*]
+            $($then:tt)*
+        ) => (
+            $crate::ඞ::lint_unless_mentions_polonius_lifetime! {
+                [$($inner)* $($rest)*]
+                $($then)*
+            }
+        );
+
+        (
+            [[ $($inner:tt)* ] $($rest:tt)*]
+            $($then:tt)*
+        ) => (
+            $crate::ඞ::lint_unless_mentions_polonius_lifetime! {
+                [$($inner)* $($rest)*]
+                $($then)*
+            }
+        );
+
+        (
+            [$skipped:tt $($rest:tt)*]
+            $($then:tt)*
+        ) => (
+            $crate::ඞ::lint_unless_mentions_polonius_lifetime! {
+                [$($rest)*]
+                $($then)*
+            }
+        );
+
+        (
+            []
+            $($then:tt)*
+        ) => ({
+            let () = $crate::ඞ::return_type_not_mentioning_polonius;
+            $($then)*
+        });
+    } pub use ඞ_lint_unless_mentions_polonius_lifetime as lint_unless_mentions_polonius_lifetime;
+
+    #[doc(hidden)] /** Not part of the public API */ #[macro_export]
+    macro_rules! ඞ_polonius_unchecked {(
+        // `[polonius]`, or `[polonius_dyn &mut]`.
+        [$entry:ident $($by_ref:tt)*]
+        // `[]`, or `['label]`.
+        [$($label:lifetime)?]
+        |$var:ident| -> $Ret:ty
+            $body:block
+    ) => ($crate::ඞ::if_polonius! {
+        native {{
+            // same `mut`-ness requirements as the emulated expansion.
+            #[allow(clippy::self_assignment)] {
+                $var = $var;
+            }
+            // `exit_polonius!` `break`s out of this single-iteration `loop`.
+            #[allow(unused_labels, clippy::never_loop)]
+            let value = 'exit_polonius: loop {
+                #[allow(unused_imports)]
+                use $crate::ඞ::polonius_scope::*;
+                $crate::ඞ::first! {
+                    $((
+                        $crate::ඞ::native_scope! {
+                            return: [break $label],
+                            break: 'exit_polonius,
+                        }
+                    ) (if $label))?
+                    (
+                        $crate::ඞ::native_scope! {
+                            return: [return],
+                            break: 'exit_polonius,
+                        }
+                    )
+                }
+                break if true
+                    $body
+                else {
+                    // avoid a dead-code warning
+                    $crate::ඞ::None.unwrap()
+                };
+            };
+            value
+        }}
+        else {
+            match
+                $crate::$entry::<
+                    _,
+                    _,
+                    $crate::ඞ::first! {
+                        $((
+                            // the function's return type, for `?` to convert
+                            // its residuals into, is inferred from the
+                            // `Propagate` arm below.
+                            $crate::ForLt!(<'polonius> = $crate::ඞ::Dependent<
+                                $Ret,
+                                $crate::ඞ::Never,
+                                _,
+                            >)
+                        ) (if $label))?
+                        (
+                            $crate::ForLt!(<'polonius> = $crate::ඞ::Dependent<$Ret>)
+                        )
+                    },
+                >(
+                    $var,
+                    $($by_ref)* |mut $var: &mut _| {
+                        #[allow(unused_imports)]
+                        use $crate::ඞ::polonius_scope::*;
+                        // silence the unused `mut` warning.
+                        #[allow(clippy::self_assignment)] {
+                            $var = $var;
+                        }
+                        $crate::PoloniusResult::Owned(
+                            if true
+                                $body
+                            else {
+                                // avoid a dead-code warning
+                                $crate::ඞ::None.unwrap()
+                            }
+                        )
+                    },
+                )
+            {
+                | $crate::PoloniusResult::Borrowing(ret) => $crate::ඞ::first! {
+                    $((
+                        match ret {
+                            | $crate::ඞ::Dependent::Return(it) => break $label it,
+                            | $crate::ඞ::Dependent::Propagate(it) => return it,
+                            | $crate::ඞ::Dependent::Break(unreachable) => match unreachable {},
+                        }
+                    ) (if $label))?
+                    (
+                        return ret.return_no_break()
+                    )
+                },
+                | $crate::PoloniusResult::Owned { value, input_borrow, .. } => {
+                    $var = input_borrow;
+                    value
+                },
+            }
+        }
+    })} pub use ඞ_polonius_unchecked as polonius_unchecked;
+
+    #[doc(hidden)] /** Not part of the public API */ #[macro_export]
+    macro_rules! ඞ_polonius_loop_unchecked {(
+        // `[polonius]`, or `[polonius_dyn &mut]`.
+        [$entry:ident $($by_ref:tt)*]
+        | $var:ident | -> $Ret:ty $(, break: $Break:ty)?
+            $body:block
+        $(,)?
+    ) => ($crate::ඞ::if_polonius! {
+        native {
+            'polonius_loop: loop {
+                #[allow(unused_imports)]
+                use $crate::ඞ::polonius_loop_scope::*;
+                $crate::ඞ::native_scope! {
+                    return: [return],
+                    break: 'polonius_loop,
+                }
+                // (uses the label, whether the body does or not)
+                if false {
+                    continue 'polonius_loop;
+                }
+                // same `mut`-ness requirements as the emulated expansion.
+                #[allow(clippy::self_assignment)] {
+                    $var = $var;
+                }
+                $crate::ඞ::polonius_loop_body(
+                    if true
+                        $body
+                    else {
+                        // avoid a dead-code warning
+                        $crate::ඞ::core::option::Option::None.unwrap()
+                    }
+                );
+            }
+        }
+        else {
+            loop {
+                match
+                    $crate::$entry::<
+                        _,
+                        _,
+                        $crate::ForLt!(<'polonius>
+                            = $crate::ඞ::Dependent< $Ret $(, $Break)? >
+                        ),
+                    >(
+                        &mut *$var,
+                        $($by_ref)* |mut $var: &mut _| {
+                            #[allow(unused_imports)]
+                            use $crate::ඞ::polonius_loop_scope::*;
+                            // silence the unused `mut` warning.
+                            #[allow(clippy::self_assignment)] {
+                                $var = $var;
+                            }
+                            $crate::ඞ::polonius_loop_body(
+                                if true
+                                    $body
+                                else {
+                                    // avoid a dead-code warning
+                                    $crate::ඞ::core::option::Option::None.unwrap()
+                                }
+                            );
+                            $crate::polonius_continue!();
+                        },
+                    )
+                {
+                    | $crate::PoloniusResult::Borrowing(dependent) => match dependent {
+                        | $crate::ඞ::Dependent::Return(return_value)
+                        | $crate::ඞ::Dependent::Propagate(return_value) => return return_value,
+                        | $crate::ඞ::Dependent::Break(break_value) => $crate::ඞ::first! {
+                            $((
+                                break if false { loop {} } else { break_value }
+                            ) (if $Break type else))? ({
+                                let _: $crate::ඞ::dependent_break_without_break_ty_annotation = break_value;
+                                match break_value {}
+                            })
+                        },
+                    },
+                    | $crate::PoloniusResult::Owned { value, input_borrow, .. } => {
+                        $var = input_borrow;
+                        match value {
+                            | $crate::ඞ::core::ops::ControlFlow::Break(value) => {
+                                break if false { loop {} } else { value };
+                            },
+                            | $crate::ඞ::core::ops::ControlFlow::Continue(()) => continue,
+                        }
+                    },
+                }
+            }
+        }
+    })} pub use ඞ_polonius_loop_unchecked as polonius_loop_unchecked;
+
+    // Picks the `native` expansion when the `polonius` feature is enabled,
+    // and the `else` (closure-based, emulated) one otherwise.
+    //
+    // The `native` expansions are the straight-line code (`return`, `break`,
+    // `continue`) which `-Zpolonius` accepts as is.
+    #[cfg(feature = "polonius")]
+    #[doc(hidden)] /** Not part of the public API */ #[macro_export]
+    macro_rules! ඞ_if_polonius {(
+        native { $($native:tt)* }
+        else { $($emulated:tt)* }
+    ) => (
+        $($native)*
+    )}
+
+    #[cfg(not(feature = "polonius"))]
+    #[doc(hidden)] /** Not part of the public API */ #[macro_export]
+    macro_rules! ඞ_if_polonius {(
+        native { $($native:tt)* }
+        else { $($emulated:tt)* }
+    ) => (
+        $($emulated)*
+    )}
+
+    pub use ඞ_if_polonius as if_polonius;
+
+    #[doc(hidden)] /** Not part of the public API */ #[macro_export]
+    macro_rules! ඞ_polonius_first_of_chain {
+        (
+            $input_borrow:ident, $Ret:ty,
+            [] |$else_binding:ident| $else:block
+        ) => ({
+            let $else_binding = $input_borrow;
+            $else
+        });
+
+        (
+            $input_borrow:ident, $Ret:ty,
+            [ |$binding:ident| $lookup:expr $(, |$bindings:ident| $lookups:expr)* ]
+            |$else_binding:ident| $else:block
+        ) => (
+            match
+                $crate::polonius::<
+                    _,
+                    _,
+                    $crate::ForLt!(<'polonius> = $Ret),
+                >(
+                    $input_borrow,
+                    |$binding: &mut _| match $lookup {
+                        | $crate::ඞ::Some(hit) => $crate::PoloniusResult::Borrowing(hit),
+                        | $crate::ඞ::None => $crate::PoloniusResult::Owned(()),
+                    },
+                )
+            {
+                | $crate::PoloniusResult::Borrowing(hit) => hit,
+                | $crate::PoloniusResult::Owned { input_borrow, .. } => {
+                    $crate::ඞ::polonius_first_of_chain! {
+                        input_borrow, $Ret,
+                        [$( |$bindings| $lookups ),*] |$else_binding| $else
+                    }
+                },
+            }
+        );
+    } pub use ඞ_polonius_first_of_chain as polonius_first_of_chain;
+
     #[doc(hidden)] /** Not part of the public API */ #[macro_export]
     macro_rules! ඞ_first {(
         ( $($tt:tt)* )
@@ -0,0 +1,221 @@
+//! The README's `get_or_insert` technique, once and for all, for any
+//! [`MapLike`] container.
+//!
+//! Arenas, slot maps, sorted-vector maps…: implementing [`MapLike::get_mut()`]
+//! and [`MapLike::insert()`] for such a container is enough to be able to use
+//! the [`get_or_insert_with()`] and [`get_or_try_insert_with()`] helpers on it.
+//!
+//! ## Example
+//!
+/*!  - ```rust
+    use ::polonius_the_crab::map_like::{self, MapLike};
+
+    /// A map backed by a `Vec` of entries sorted by key.
+    struct SortedVecMap<K, V>(Vec<(K, V)>);
+
+    impl<K : Ord, V> MapLike for SortedVecMap<K, V> {
+        type Key = K;
+        type Value = V;
+
+        fn get_mut (&mut self, key: &K)
+          -> Option<&mut V>
+        {
+            let idx = self.0.binary_search_by(|(k, _)| k.cmp(key)).ok()?;
+            Some(&mut self.0[idx].1)
+        }
+
+        fn insert (&mut self, key: K, value: V)
+        {
+            match self.0.binary_search_by(|(k, _)| k.cmp(&key)) {
+                | Ok(idx) => self.0[idx].1 = value,
+                | Err(idx) => self.0.insert(idx, (key, value)),
+            }
+        }
+    }
+
+    let mut map = SortedVecMap(vec![]);
+    for word in ["b", "a", "b"] {
+        *map_like::get_or_insert_with(&mut map, word, || 0) += 1;
+    }
+    assert_eq!(map.0, [("a", 1), ("b", 2)]);
+    ``` */
+
+use crate::{
+    Fallible,
+    ForLt,
+    polonius,
+    PoloniusResult,
+    Residual,
+    Try,
+};
+
+/// The minimal API of a map-like container for [`get_or_insert_with()`] and
+/// [`get_or_try_insert_with()`] to be usable with it.
+///
+/// Implemented for [`BTreeMap`][::alloc::collections::BTreeMap] (with the
+/// `alloc` feature), for [`HashMap`][::std::collections::HashMap] (with the
+/// `std` feature), and for [`LruCache`][crate::lru::LruCache].
+pub
+trait MapLike {
+    /// The type of the keys.
+    type Key;
+
+    /// The type of the values.
+    type Value;
+
+    /// Returns the value for `key`, if any.
+    fn get_mut (&mut self, key: &'_ Self::Key)
+      -> Option<&'_ mut Self::Value>
+    ;
+
+    /// Inserts `value` under `key`, replacing the previous value, if any.
+    fn insert (&mut self, key: Self::Key, value: Self::Value)
+    ;
+
+    /// Inserts `value` under `key`, and returns a borrow to it.
+    ///
+    /// The provided implementation [`insert`][Self::insert]s a clone of `key`,
+    /// and then looks the value up with [`get_mut`][Self::get_mut]:
+    /// containers featuring an `.entry()`-like API ought to override it to
+    /// save that second lookup.
+    fn insert_and_get_mut (&mut self, key: Self::Key, value: Self::Value)
+      -> &'_ mut Self::Value
+    where
+        Self::Key : Clone,
+    {
+        self.insert(key.clone(), value);
+        self.get_mut(&key)
+            .expect("`MapLike::get_mut()` to find the just inserted value")
+    }
+}
+
+/// Returns the value for `key`, inserting `make_value()` on a miss.
+///
+/// The `M::Key : Clone` bound is that of [`MapLike::insert_and_get_mut()`],
+/// through which the insertion happens.
+pub
+fn get_or_insert_with<M : ?Sized + MapLike> (
+    map: &'_ mut M,
+    key: M::Key,
+    make_value: impl FnOnce() -> M::Value,
+) -> &'_ mut M::Value
+where
+    M::Key : Clone,
+{
+    match lookup(map, &key) {
+        | PoloniusResult::Borrowing(value) => value,
+        | PoloniusResult::Owned { input_borrow: map, .. } => {
+            map.insert_and_get_mut(key, make_value())
+        },
+    }
+}
+
+/// Fallible version of [`get_or_insert_with()`]: an error (or a [`None`])
+/// returned by `make_value` is propagated, and nothing gets inserted.
+///
+/// Works with any [`Result`] or [`Option`]-returning `make_value`.
+pub
+fn get_or_try_insert_with<M : ?Sized + MapLike, R> (
+    map: &'_ mut M,
+    key: M::Key,
+    make_value: impl FnOnce() -> R,
+) -> <R::Residual as Residual>::WithOutput<&'_ mut M::Value>
+where
+    M::Key : Clone,
+    R : Fallible<Output = M::Value>,
+{
+    match lookup(map, &key) {
+        | PoloniusResult::Borrowing(value) => {
+            <R::Residual as Residual>::from_output(value)
+        },
+        | PoloniusResult::Owned { input_borrow: map, .. } => {
+            match Try::branch(make_value()) {
+                | Ok(value) => {
+                    <R::Residual as Residual>::from_output(
+                        map.insert_and_get_mut(key, value)
+                    )
+                },
+                | Err(residual) => Residual::with_output(residual),
+            }
+        },
+    }
+}
+
+fn lookup<'r, M : ?Sized + MapLike> (map: &'r mut M, key: &'_ M::Key)
+  -> PoloniusResult<&'r mut M::Value, (), &'r mut M>
+{
+    polonius::<_, _, ForLt!(<'v> = &'v mut M::Value)>(map, |map| {
+        match map.get_mut(key) {
+            | Some(value) => PoloniusResult::Borrowing(value),
+            | None => PoloniusResult::Owned(()),
+        }
+    })
+}
+
+#[cfg(feature = "alloc")]
+impl<K : Ord, V> MapLike for ::alloc::collections::BTreeMap<K, V> {
+    type Key = K;
+    type Value = V;
+
+    #[inline]
+    fn get_mut (&mut self, key: &'_ K)
+      -> Option<&'_ mut V>
+    {
+        Self::get_mut(self, key)
+    }
+
+    #[inline]
+    fn insert (&mut self, key: K, value: V)
+    {
+        Self::insert(self, key, value);
+    }
+
+    fn insert_and_get_mut (&mut self, key: K, value: V)
+      -> &'_ mut V
+    {
+        use ::alloc::collections::btree_map::Entry;
+        match self.entry(key) {
+            | Entry::Occupied(mut entry) => {
+                entry.insert(value);
+                entry.into_mut()
+            },
+            | Entry::Vacant(entry) => entry.insert(value),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> MapLike for ::std::collections::HashMap<K, V, S>
+where
+    K : ::core::hash::Hash + Eq,
+    S : ::core::hash::BuildHasher,
+{
+    type Key = K;
+    type Value = V;
+
+    #[inline]
+    fn get_mut (&mut self, key: &'_ K)
+      -> Option<&'_ mut V>
+    {
+        Self::get_mut(self, key)
+    }
+
+    #[inline]
+    fn insert (&mut self, key: K, value: V)
+    {
+        Self::insert(self, key, value);
+    }
+
+    fn insert_and_get_mut (&mut self, key: K, value: V)
+      -> &'_ mut V
+    {
+        use ::std::collections::hash_map::Entry;
+        match self.entry(key) {
+            | Entry::Occupied(mut entry) => {
+                entry.insert(value);
+                entry.into_mut()
+            },
+            | Entry::Vacant(entry) => entry.insert(value),
+        }
+    }
+}
@@ -0,0 +1,289 @@
+//! Memoization caches whose lookups return long-lived borrows.
+//!
+//! The most pervasive instance of the lack-of-Polonius issue: "if the value
+//! is cached, `return` a borrow to it; else compute it, insert it, and
+//! `return` a borrow to it", all within a single `&mut self` method.
+//!
+//! ```rust
+//! use ::polonius_the_crab::memo::Memo;
+//!
+//! let mut squares = Memo::<u64, u64>::new();
+//! assert_eq!(*squares.get_or_compute(3, |&n| n * n), 9);
+//! // cache hit: the closure is not called.
+//! assert_eq!(*squares.get_or_compute(3, |_| unreachable!()), 9);
+//! ```
+
+use {
+    ::core::{
+        hash::{BuildHasher, Hash},
+        marker::PhantomData,
+    },
+    ::std::{
+        collections::{BTreeMap, HashMap},
+    },
+    crate::{
+        Fallible,
+        ForLt,
+        polonius,
+        PoloniusResult,
+        Residual,
+        Try,
+    },
+};
+
+/// The backing storage of a [`Memo`].
+///
+/// Implemented for [`HashMap`] and [`BTreeMap`]; implement it for your own
+/// containers to use them as a [`Memo`] backend.
+pub
+trait MemoMap<K, V> {
+    /// Look the `key` up.
+    fn get (&self, key: &'_ K)
+      -> Option<&'_ V>
+    ;
+
+    /// Insert `value` under `key` (overwriting any previous value), and
+    /// return a borrow to it.
+    fn insert (&mut self, key: K, value: V)
+      -> &'_ mut V
+    ;
+}
+
+impl<K, V, S> MemoMap<K, V> for HashMap<K, V, S>
+where
+    K : Hash + Eq,
+    S : BuildHasher,
+{
+    #[inline]
+    fn get (&self, key: &'_ K)
+      -> Option<&'_ V>
+    {
+        HashMap::get(self, key)
+    }
+
+    fn insert (&mut self, key: K, value: V)
+      -> &'_ mut V
+    {
+        use ::std::collections::hash_map::Entry;
+        match self.entry(key) {
+            | Entry::Occupied(mut entry) => {
+                entry.insert(value);
+                entry.into_mut()
+            },
+            | Entry::Vacant(entry) => entry.insert(value),
+        }
+    }
+}
+
+impl<K, V> MemoMap<K, V> for BTreeMap<K, V>
+where
+    K : Ord,
+{
+    #[inline]
+    fn get (&self, key: &'_ K)
+      -> Option<&'_ V>
+    {
+        BTreeMap::get(self, key)
+    }
+
+    fn insert (&mut self, key: K, value: V)
+      -> &'_ mut V
+    {
+        use ::std::collections::btree_map::Entry;
+        match self.entry(key) {
+            | Entry::Occupied(mut entry) => {
+                entry.insert(value);
+                entry.into_mut()
+            },
+            | Entry::Vacant(entry) => entry.insert(value),
+        }
+    }
+}
+
+/// A memoization cache, mapping `K`eys to their (computed once) `V`alues.
+///
+/// Generic over its backing map `M` (see [`MemoMap`]).
+///
+/// ## Example
+///
+/**  - ```rust
+    use ::polonius_the_crab::memo::Memo;
+
+    fn fib (memo: &'_ mut Memo<u32, u64>, n: u32)
+      -> &'_ u64
+    {
+        memo.get_or_compute_recursive(n, |memo, &n| match n {
+            | 0 | 1 => n.into(),
+            | _ => *fib(memo, n - 1) + *fib(memo, n - 2),
+        })
+    }
+
+    assert_eq!(*fib(&mut Memo::new(), 90), 2_880_067_194_370_816_120);
+    ``` */
+pub
+struct Memo<K, V, M = HashMap<K, V>> {
+    map: M,
+    _phantom: PhantomData<fn(K) -> V>,
+}
+
+impl<K, V, M : Default> Default for Memo<K, V, M> {
+    #[inline]
+    fn default ()
+      -> Self
+    {
+        Self::with_map(M::default())
+    }
+}
+
+impl<K, V, M : Default> Memo<K, V, M> {
+    /// Creates an empty cache.
+    #[inline]
+    pub
+    fn new ()
+      -> Self
+    {
+        Self::default()
+    }
+}
+
+impl<K, V, M> Memo<K, V, M> {
+    /// Creates a cache backed by the given `map`, which may thus be prefilled.
+    #[inline]
+    pub
+    const
+    fn with_map (map: M)
+      -> Self
+    {
+        Self {
+            map,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Read-only access to the backing map.
+    #[inline]
+    pub
+    fn map (&self)
+      -> &'_ M
+    {
+        &self.map
+    }
+
+    /// Extracts the backing map.
+    #[inline]
+    pub
+    fn into_map (self)
+      -> M
+    {
+        self.map
+    }
+}
+
+impl<K, V, M : MemoMap<K, V>> Memo<K, V, M> {
+    /// Returns the cached value for `key`, if any.
+    #[inline]
+    pub
+    fn get (&self, key: &'_ K)
+      -> Option<&'_ V>
+    {
+        self.map.get(key)
+    }
+
+    /// Returns the cached value for `key`, computing (and caching) it with
+    /// `compute` on a miss.
+    pub
+    fn get_or_compute (
+        &mut self,
+        key: K,
+        compute: impl FnOnce(&K) -> V,
+    ) -> &'_ V
+    {
+        match self.lookup(&key) {
+            | PoloniusResult::Borrowing(value) => value,
+            | PoloniusResult::Owned { input_borrow: map, .. } => {
+                let value = compute(&key);
+                map.insert(key, value)
+            },
+        }
+    }
+
+    /// Fallible version of [`Self::get_or_compute()`]: an error (or a
+    /// [`None`]) returned by `compute` is propagated, and nothing gets cached.
+    ///
+    /// Works with any [`Result`] or [`Option`]-returning `compute`.
+    ///
+    /// ```rust
+    /// use ::polonius_the_crab::memo::Memo;
+    ///
+    /// let mut parsed = Memo::<&str, i32>::new();
+    /// let parse = |s: &&str| s.parse::<i32>();
+    /// assert_eq!(parsed.get_or_try_compute("42", parse), Ok(&42));
+    /// assert!(parsed.get_or_try_compute("nope", parse).is_err());
+    /// assert!(parsed.get(&"nope").is_none());
+    /// ```
+    pub
+    fn get_or_try_compute<R> (
+        &mut self,
+        key: K,
+        compute: impl FnOnce(&K) -> R,
+    ) -> <R::Residual as Residual>::WithOutput<&'_ V>
+    where
+        R : Fallible<Output = V>,
+    {
+        match self.lookup(&key) {
+            | PoloniusResult::Borrowing(value) => {
+                <R::Residual as Residual>::from_output(value)
+            },
+            | PoloniusResult::Owned { input_borrow: map, .. } => {
+                match Try::branch(compute(&key)) {
+                    | Ok(value) => {
+                        <R::Residual as Residual>::from_output(
+                            &*map.insert(key, value)
+                        )
+                    },
+                    | Err(residual) => Residual::with_output(residual),
+                }
+            },
+        }
+    }
+
+    /// Like [`Self::get_or_compute()`], but for `compute` to be able to
+    /// query the cache itself, _e.g._, to recurse.
+    ///
+    /// Should `compute` have cached a value for `key` in the meantime, it gets
+    /// overwritten with the returned one.
+    pub
+    fn get_or_compute_recursive (
+        &mut self,
+        key: K,
+        compute: impl FnOnce(&mut Self, &K) -> V,
+    ) -> &'_ V
+    {
+        match polonius::<_, _, ForLt!(<'r> = &'r V)>(
+            self,
+            |this| match this.map.get(&key) {
+                | Some(value) => PoloniusResult::Borrowing(value),
+                | None => PoloniusResult::Owned(()),
+            },
+        )
+        {
+            | PoloniusResult::Borrowing(value) => value,
+            | PoloniusResult::Owned { input_borrow: this, .. } => {
+                let value = compute(this, &key);
+                this.map.insert(key, value)
+            },
+        }
+    }
+
+    fn lookup (&mut self, key: &'_ K)
+      -> PoloniusResult<&'_ V, (), &'_ mut M>
+    {
+        polonius::<_, _, ForLt!(<'r> = &'r V)>(
+            &mut self.map,
+            |map| match map.get(key) {
+                | Some(value) => PoloniusResult::Borrowing(value),
+                | None => PoloniusResult::Owned(()),
+            },
+        )
+    }
+}
@@ -0,0 +1,268 @@
+//! Backtracking helpers for hand-written (recursive-descent) parsers.
+//!
+//! "Try a production; on success, return a token / node borrowing from the
+//! cursor; on failure, restore the cursor and try the next alternative" does
+//! not compile without Polonius:
+//!
+/*!  - ```rust ,compile_fail
+    # struct Cursor { pos: usize, scratch: String }
+    # fn quoted (c: &mut Cursor) -> Option<&str> { None }
+    # fn word (c: &mut Cursor) -> Option<&str> { None }
+    fn string_or_word (cursor: &'_ mut Cursor)
+      -> Option<&'_ str>
+    {
+        let pos = cursor.pos;
+        if let Some(s) = quoted(cursor) {
+            return Some(s);
+        }
+        cursor.pos = pos;
+        word(cursor)
+    }
+    ``` */
+//!
+//! Hence [`try_alternatives()`] and [`polonius_alt!`], for cursors
+//! implementing [`Backtrack`].
+//!
+//! [`polonius_alt!`]: crate::polonius_alt!
+
+use crate::{
+    ForLt,
+    polonius,
+    PoloniusResult,
+};
+
+/// A parser cursor whose position can be saved, and later restored.
+pub
+trait Backtrack {
+    /// The saved state of the cursor.
+    type Checkpoint;
+
+    /// Saves the current state of the cursor.
+    fn checkpoint (&self)
+      -> Self::Checkpoint
+    ;
+
+    /// Goes back to a previously saved state.
+    fn restore (&mut self, checkpoint: Self::Checkpoint)
+    ;
+}
+
+/// The remaining input is the cursor.
+impl<'src> Backtrack for &'src str {
+    type Checkpoint = &'src str;
+
+    #[inline]
+    fn checkpoint (&self)
+      -> &'src str
+    {
+        self
+    }
+
+    #[inline]
+    fn restore (&mut self, checkpoint: &'src str)
+    {
+        *self = checkpoint;
+    }
+}
+
+/// The remaining input is the cursor.
+impl<'src, T> Backtrack for &'src [T] {
+    type Checkpoint = &'src [T];
+
+    #[inline]
+    fn checkpoint (&self)
+      -> &'src [T]
+    {
+        self
+    }
+
+    #[inline]
+    fn restore (&mut self, checkpoint: &'src [T])
+    {
+        *self = checkpoint;
+    }
+}
+
+/// An alternative (_e.g._, a production) for [`try_alternatives()`]: parses
+/// an `Output` off the cursor, if possible.
+pub
+type Alternative<'alt, C, Output> =
+    dyn 'alt + for<'any> FnMut(&'any mut C) -> Option<<Output as ForLt>::Of<'any>>
+;
+
+/// Runs each of the `alternatives` in turn, starting from the current state
+/// of the `cursor`, until one of them succeeds: its output is returned, with
+/// the `cursor` left as that alternative left it.
+///
+/// Otherwise, the `cursor` is given back (as an `Err`), unchanged.
+///
+/// The `Output` type is to be turbofished, as a [`ForLt!`] type. See also
+/// [`polonius_alt!`] for a more convenient syntax.
+///
+/// ## Example
+///
+/**  - ```rust
+    use ::polonius_the_crab::{ForLt, parse::try_alternatives};
+
+    fn tag<'c> (cursor: &'c mut &str, tag: &'static str)
+      -> Option<&'static str>
+    {
+        *cursor = cursor.strip_prefix(tag)?;
+        Some(tag)
+    }
+
+    let mut cursor = "false";
+    let res = try_alternatives::<_, ForLt!(<'r> = &'static str)>(&mut cursor, &mut [
+        &mut |c| tag(c, "true"),
+        &mut |c| tag(c, "false"),
+    ]);
+    assert_eq!(res.ok(), Some("false"));
+    assert_eq!(cursor, "");
+    ``` */
+///
+/// [`ForLt!`]: crate::ForLt!
+/// [`polonius_alt!`]: crate::polonius_alt!
+pub
+fn try_alternatives<'c, 'alt, C : ?Sized + Backtrack, Output : ?Sized> (
+    mut cursor: &'c mut C,
+    alternatives: &mut [&'alt mut Alternative<'alt, C, Output>],
+) -> Result<Output::Of<'c>, &'c mut C>
+where
+    Output : 'alt + ForLt,
+{
+    for alternative in alternatives {
+        let checkpoint = cursor.checkpoint();
+        match polonius::<_, _, Output>(cursor, |cursor| match alternative(cursor) {
+            | Some(output) => PoloniusResult::Borrowing(output),
+            | None => PoloniusResult::Owned(()),
+        })
+        {
+            | PoloniusResult::Borrowing(output) => return Ok(output),
+            | PoloniusResult::Owned { input_borrow, .. } => {
+                cursor = input_borrow;
+                cursor.restore(checkpoint);
+            },
+        }
+    }
+    Err(cursor)
+}
+
+/// Sugar for [`try_alternatives()`]: evaluates to the output of the first
+/// successful alternative, or else to the `else` block.
+///
+/// Each alternative is an expression using the given `&mut` cursor variable,
+/// and evaluating to an <code>[Option]\<Ret\<\'polonius\>\></code>. Within the
+/// `else` block, the cursor variable is usable again (and unchanged), as after
+/// a [`polonius!`] block.
+///
+/// With the `polonius` Cargo feature, the alternatives are tried inline, as
+/// with the other macros, rather than through [`try_alternatives()`].
+///
+/// ## Example
+///
+/**  - ```rust
+    use ::polonius_the_crab::{parse::Backtrack, polonius_alt};
+
+    /// A cursor which unescapes string literals into its own scratch buffer.
+    struct Cursor<'src> {
+        rest: &'src str,
+        scratch: String,
+    }
+
+    impl<'src> Backtrack for Cursor<'src> {
+        type Checkpoint = &'src str;
+
+        fn checkpoint (&self) -> &'src str { self.rest }
+        fn restore (&mut self, rest: &'src str) { self.rest = rest; }
+    }
+
+    impl<'src> Cursor<'src> {
+        /// `"…"`, with `\` escapes.
+        fn string (&mut self) -> Option<&str> {
+            let mut chars = self.rest.strip_prefix('"')?.char_indices();
+            self.scratch.clear();
+            while let Some((i, c)) = chars.next() {
+                match c {
+                    | '"' => {
+                        self.rest = &self.rest[i + 2 ..];
+                        return Some(&self.scratch);
+                    },
+                    | '\\' => self.scratch.push(chars.next()?.1),
+                    | _ => self.scratch.push(c),
+                }
+            }
+            None
+        }
+
+        fn word (&mut self) -> Option<&str> {
+            let end = self.rest.find(|c: char| !c.is_alphanumeric()).unwrap_or(self.rest.len());
+            let (word, rest) = self.rest.split_at(end);
+            self.rest = rest;
+            (!word.is_empty()).then_some(word)
+        }
+    }
+
+    fn atom<'c> (mut cursor: &'c mut Cursor<'_>)
+      -> Result<&'c str, String>
+    {
+        Ok(polonius_alt!(|cursor| -> &'polonius str {
+            cursor.string(),
+            cursor.word(),
+        } else {
+            // `cursor` is usable again, and unchanged.
+            return Err(format!("expected an atom, got `{}`", cursor.rest));
+        }))
+    }
+
+    let mut cursor = Cursor { rest: r#""a\"b" c "unterminated"#, scratch: String::new() };
+    assert_eq!(atom(&mut cursor).unwrap(), r#"a"b"#);
+    cursor.rest = cursor.rest.trim_start();
+    assert_eq!(atom(&mut cursor).unwrap(), "c");
+    cursor.rest = cursor.rest.trim_start();
+    assert_eq!(atom(&mut cursor).unwrap_err(), r#"expected an atom, got `"unterminated`"#);
+    ``` */
+///
+/// [`polonius!`]: crate::polonius!
+#[macro_export]
+macro_rules! polonius_alt {(
+    |$var:ident $(,)?| -> $Ret:ty {
+        $( $alternative:expr ),+ $(,)?
+    } else $else:block $(,)?
+) => ($crate::ඞ::if_polonius! {
+    native {{
+        let input_borrow: &mut _ = $var;
+        'polonius_alt: {
+            $({
+                let checkpoint = $crate::parse::Backtrack::checkpoint(&*input_borrow);
+                {
+                    let $var: &mut _ = &mut *input_borrow;
+                    if let $crate::ඞ::Some(output) = $alternative {
+                        break 'polonius_alt output;
+                    }
+                }
+                $crate::parse::Backtrack::restore(&mut *input_borrow, checkpoint);
+            })+
+            $var = input_borrow;
+            $else
+        }
+    }}
+    else {
+        match
+            $crate::parse::try_alternatives::<
+                _,
+                $crate::ForLt!(<'polonius> = $Ret),
+            >(
+                $var,
+                &mut [$(
+                    &mut |$var: &mut _| $alternative
+                ),+],
+            )
+        {
+            | $crate::ඞ::Ok(output) => output,
+            | $crate::ඞ::Err(input_borrow) => {
+                $var = input_borrow;
+                $else
+            },
+        }
+    }
+})}
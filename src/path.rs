@@ -0,0 +1,155 @@
+//! Navigating nested structures through `&mut` borrows, creating the missing
+//! levels on the way down (_autovivification_).
+//!
+//! Each level is a "get the existing child, or else create it" step, _i.e._,
+//! yet another conditional-return-of-a-borrow, which does not compile without
+//! Polonius when the child is looked up by reference (so as not to clone the
+//! key on hits):
+//!
+/*!  - ```rust ,compile_fail
+    use ::std::collections::HashMap;
+
+    fn child<'m> (map: &'m mut HashMap<String, Vec<u8>>, key: &str)
+      -> &'m mut Vec<u8>
+    {
+        if let Some(child) = map.get_mut(key) {
+            return child;
+        }
+        map.entry(key.to_owned()).or_default()
+    }
+    ``` */
+//!
+//! With this module, every such [`Path::step()`] is performed through
+//! [`polonius()`], and so are the steps of [`Path::descend()`] through
+//! recursive structures.
+//!
+//! ## Example
+//!
+/*!  - ```rust
+    use {
+        ::polonius_the_crab::path::path,
+        ::std::collections::HashMap,
+    };
+
+    type Index = HashMap<String, HashMap<String, Vec<u32>>>;
+
+    fn entries<'i> (index: &'i mut Index, lang: &str, word: &str)
+      -> &'i mut Vec<u32>
+    {
+        path(index)
+            .step(|by_lang| by_lang.get_mut(lang), |by_lang| by_lang.entry(lang.into()).or_default())
+            .step(|by_word| by_word.get_mut(word), |by_word| by_word.entry(word.into()).or_default())
+            .into_mut()
+    }
+
+    let mut index = Index::new();
+    entries(&mut index, "en", "crab").push(1);
+    entries(&mut index, "en", "crab").push(2);
+    entries(&mut index, "fr", "crabe").push(3);
+    assert_eq!(index["en"]["crab"], [1, 2]);
+    assert_eq!(index["fr"]["crabe"], [3]);
+    ``` */
+
+use crate::{
+    ForLt,
+    polonius,
+    PoloniusResult,
+};
+
+/// A `&mut` borrow of some level of a nested structure, to navigate further
+/// down with [`.step()`][Self::step] or [`.descend()`][Self::descend].
+pub
+struct Path<'r, T : ?Sized> {
+    node: &'r mut T,
+}
+
+/// Starts navigating from the given `root`.
+pub
+fn path<T : ?Sized> (root: &'_ mut T)
+  -> Path<'_, T>
+{
+    Path { node: root }
+}
+
+impl<'r, T : ?Sized> Path<'r, T> {
+    /// Goes down one level: to the child that `lookup` returns, or else to the
+    /// one that `create` inserts (and returns).
+    pub
+    fn step<U : ?Sized> (
+        self,
+        lookup: impl FnOnce(&mut T) -> Option<&mut U>,
+        create: impl FnOnce(&mut T) -> &mut U,
+    ) -> Path<'r, U>
+    {
+        let node = match polonius::<_, _, ForLt!(<'n> = &'n mut U)>(self.node, |node| {
+            match lookup(node) {
+                | Some(child) => PoloniusResult::Borrowing(child),
+                | None => PoloniusResult::Owned(()),
+            }
+        }) {
+            | PoloniusResult::Borrowing(child) => child,
+            | PoloniusResult::Owned { input_borrow: node, .. } => create(node),
+        };
+        Path { node }
+    }
+
+    /// Goes down one level per `key`, in a recursive structure (such as a
+    /// tree, or a JSON-like `enum`): to the child that `lookup` returns, or
+    /// else to the one that `create` inserts (and returns).
+    ///
+    /// ```rust
+    /// use {
+    ///     ::polonius_the_crab::path::path,
+    ///     ::std::collections::BTreeMap,
+    /// };
+    ///
+    /// #[derive(Debug, Default, PartialEq)]
+    /// struct Dir {
+    ///     files: Vec<&'static str>,
+    ///     subdirs: BTreeMap<String, Dir>,
+    /// }
+    ///
+    /// let mut root = Dir::default();
+    /// path(&mut root)
+    ///     .descend(
+    ///         "usr/local/bin".split('/'),
+    ///         |dir, name| dir.subdirs.get_mut(*name),
+    ///         |dir, name| dir.subdirs.entry(name.into()).or_default(),
+    ///     )
+    ///     .into_mut()
+    ///     .files
+    ///     .push("crab")
+    /// ;
+    /// assert_eq!(root.subdirs["usr"].subdirs["local"].subdirs["bin"].files, ["crab"]);
+    /// ```
+    pub
+    fn descend<K> (
+        self,
+        keys: impl IntoIterator<Item = K>,
+        mut lookup: impl for<'n> FnMut(&'n mut T, &K) -> Option<&'n mut T>,
+        mut create: impl FnMut(&mut T, K) -> &mut T,
+    ) -> Path<'r, T>
+    {
+        let mut node = self.node;
+        for key in keys {
+            node = match polonius::<_, _, ForLt!(<'n> = &'n mut T)>(node, |node| {
+                match lookup(node, &key) {
+                    | Some(child) => PoloniusResult::Borrowing(child),
+                    | None => PoloniusResult::Owned(()),
+                }
+            }) {
+                | PoloniusResult::Borrowing(child) => child,
+                | PoloniusResult::Owned { input_borrow: node, .. } => create(node, key),
+            };
+        }
+        Path { node }
+    }
+
+    /// The current level, borrowed for as long as the root was.
+    pub
+    fn into_mut (self)
+      -> &'r mut T
+    {
+        self.node
+    }
+}
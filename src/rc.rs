@@ -0,0 +1,156 @@
+//! "Get `&mut`, or else …" helpers for the shared pointers ([`Rc`] and
+//! [`Arc`]), with custom, borrow-returning, fallbacks.
+//!
+//! [`Rc::make_mut()`] only supports cloning the whole value, and the manual
+//! version of it does not compile without Polonius:
+//!
+/*!  - ```rust ,compile_fail
+    use ::std::rc::Rc;
+
+    fn make_mut (rc: &'_ mut Rc<Vec<i32>>)
+      -> &'_ mut Vec<i32>
+    {
+        if let Some(unique) = Rc::get_mut(rc) {
+            return unique;
+        }
+        *rc = Rc::new(rc.iter().copied().filter(|&x| x != 0).collect());
+        Rc::get_mut(rc).unwrap()
+    }
+    ``` */
+//!
+//! Hence [`get_mut_or_else()`] and [`get_mut_or_try_else()`].
+//!
+//! [`Arc`]: ::alloc::sync::Arc
+
+use {
+    ::alloc::rc::Rc,
+    ::core::ops::Deref,
+    crate::{
+        Fallible,
+        ForLt,
+        polonius,
+        PoloniusResult,
+        Residual,
+        Try,
+    },
+};
+
+/// The shared pointers, which can be uniquely borrowed when not shared.
+///
+/// Implemented for [`Rc`] and [`Arc`][::alloc::sync::Arc].
+pub
+trait RcLike : Deref {
+    /// [`Rc::get_mut()`] or [`Arc::get_mut()`][::alloc::sync::Arc::get_mut].
+    fn get_mut (this: &'_ mut Self)
+      -> Option<&'_ mut Self::Target>
+    ;
+}
+
+impl<T : ?Sized> RcLike for Rc<T> {
+    #[inline]
+    fn get_mut (this: &'_ mut Rc<T>)
+      -> Option<&'_ mut T>
+    {
+        Rc::get_mut(this)
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<T : ?Sized> RcLike for ::alloc::sync::Arc<T> {
+    #[inline]
+    fn get_mut (this: &'_ mut ::alloc::sync::Arc<T>)
+      -> Option<&'_ mut T>
+    {
+        ::alloc::sync::Arc::get_mut(this)
+    }
+}
+
+/// Returns a `&mut` to the pointee of `rc` if it is not shared, or else
+/// whatever `fallback` returns.
+///
+/// ## Example
+///
+/**  - ```rust
+    use ::{
+        polonius_the_crab::rc::get_mut_or_else,
+        std::rc::Rc,
+    };
+
+    struct Doc { title: String, history: Vec<String> }
+
+    /// Copy-on-write, which does not bother copying the `history`.
+    fn title_mut (doc: &'_ mut Rc<Doc>)
+      -> &'_ mut String
+    {
+        &mut get_mut_or_else(doc, |doc| {
+            *doc = Rc::new(Doc { title: doc.title.clone(), history: vec![] });
+            Rc::get_mut(doc).unwrap()
+        }).title
+    }
+
+    let mut doc = Rc::new(Doc { title: "draft".into(), history: vec!["…".into()] });
+    let snapshot = Rc::clone(&doc);
+    title_mut(&mut doc).push('!');
+    assert_eq!(doc.title, "draft!");
+    assert!(doc.history.is_empty());
+    assert_eq!(snapshot.title, "draft");
+    ``` */
+pub
+fn get_mut_or_else<'r, P : ?Sized + RcLike> (
+    rc: &'r mut P,
+    fallback: impl FnOnce(&'r mut P) -> &'r mut P::Target,
+) -> &'r mut P::Target
+{
+    match get_mut(rc) {
+        | PoloniusResult::Borrowing(unique) => unique,
+        | PoloniusResult::Owned { input_borrow: rc, .. } => fallback(rc),
+    }
+}
+
+/// Fallible version of [`get_mut_or_else()`]: the `fallback` may fail (_e.g._,
+/// rather than cloning), with an `Err` (or a `None`).
+///
+/// ```rust
+/// use ::{
+///     polonius_the_crab::rc::get_mut_or_try_else,
+///     std::sync::Arc,
+/// };
+///
+/// let mut arc = Arc::new(0);
+/// *get_mut_or_try_else(&mut arc, |_| Err("shared")).unwrap() += 1;
+/// let shared = Arc::clone(&arc);
+/// assert_eq!(get_mut_or_try_else(&mut arc, |_| Err("shared")), Err("shared"));
+/// assert_eq!(get_mut_or_try_else(&mut arc, |_| None), None);
+/// # drop(shared);
+/// ```
+pub
+fn get_mut_or_try_else<'r, P : ?Sized + RcLike, R> (
+    rc: &'r mut P,
+    fallback: impl FnOnce(&'r mut P) -> R,
+) -> <R::Residual as Residual>::WithOutput<&'r mut P::Target>
+where
+    R : Fallible<Output = &'r mut P::Target>,
+{
+    match get_mut(rc) {
+        | PoloniusResult::Borrowing(unique) => {
+            <R::Residual as Residual>::from_output(unique)
+        },
+        | PoloniusResult::Owned { input_borrow: rc, .. } => {
+            match Try::branch(fallback(rc)) {
+                | Ok(it) => <R::Residual as Residual>::from_output(it),
+                | Err(residual) => Residual::with_output(residual),
+            }
+        },
+    }
+}
+
+fn get_mut<P : ?Sized + RcLike> (rc: &'_ mut P)
+  -> PoloniusResult<&'_ mut P::Target, (), &'_ mut P>
+{
+    polonius::<_, _, ForLt!(<'r> = &'r mut P::Target)>(rc, |rc| {
+        match P::get_mut(rc) {
+            | Some(unique) => PoloniusResult::Borrowing(unique),
+            | None => PoloniusResult::Owned(()),
+        }
+    })
+}
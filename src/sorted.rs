@@ -0,0 +1,180 @@
+//! Get-or-insert helpers for sorted `Vec`s, and for sorted `Vec<(K, V)>`
+//! maps in particular.
+//!
+//! The binary search is performed once: a miss yields the insertion index,
+//! as the [`Owned`][type@PoloniusResult::Owned] output of the
+//! [`polonius()`] lookup, so that the insertion does not search again.
+//!
+//! ```rust
+//! use ::polonius_the_crab::sorted;
+//!
+//! let mut counts = Vec::<(char, usize)>::new();
+//! for c in "abracadabra".chars() {
+//!     *sorted::get_or_insert_sorted(&mut counts, c, || 0) += 1;
+//! }
+//! assert_eq!(counts, [('a', 5), ('b', 2), ('c', 1), ('d', 1), ('r', 2)]);
+//! ```
+
+use {
+    ::alloc::vec::Vec,
+    crate::{
+        Fallible,
+        ForLt,
+        polonius,
+        PoloniusResult,
+        Residual,
+        Try,
+    },
+};
+
+/// Returns the element of the sorted `vec` whose key is `key`, inserting
+/// `make()` at the right position on a miss.
+///
+/// `vec` is assumed to be sorted by `key_of`, and `make()` to return an
+/// element with the given `key`.
+pub
+fn get_or_insert_sorted_by_key<'v, T, K : ?Sized + Ord> (
+    vec: &'v mut Vec<T>,
+    key: &'_ K,
+    key_of: impl FnMut(&T) -> &K,
+    make: impl FnOnce() -> T,
+) -> &'v mut T
+{
+    match search(vec, key, key_of) {
+        | PoloniusResult::Borrowing(found) => found,
+        | PoloniusResult::Owned { value: idx, input_borrow: vec } => {
+            vec.insert(idx, make());
+            &mut vec[idx]
+        },
+    }
+}
+
+/// Fallible version of [`get_or_insert_sorted_by_key()`]: an error (or a
+/// [`None`]) returned by `make` is propagated, and nothing gets inserted.
+pub
+fn get_or_try_insert_sorted_by_key<'v, T, K : ?Sized + Ord, R> (
+    vec: &'v mut Vec<T>,
+    key: &'_ K,
+    key_of: impl FnMut(&T) -> &K,
+    make: impl FnOnce() -> R,
+) -> <R::Residual as Residual>::WithOutput<&'v mut T>
+where
+    R : Fallible<Output = T>,
+{
+    match search(vec, key, key_of) {
+        | PoloniusResult::Borrowing(found) => {
+            <R::Residual as Residual>::from_output(found)
+        },
+        | PoloniusResult::Owned { value: idx, input_borrow: vec } => {
+            match Try::branch(make()) {
+                | Ok(value) => {
+                    vec.insert(idx, value);
+                    <R::Residual as Residual>::from_output(&mut vec[idx])
+                },
+                | Err(residual) => Residual::with_output(residual),
+            }
+        },
+    }
+}
+
+/// Returns the value for `key` in the sorted `map`, inserting `make()` on a
+/// miss.
+pub
+fn get_or_insert_sorted<K : Ord, V> (
+    map: &'_ mut Vec<(K, V)>,
+    key: K,
+    make: impl FnOnce() -> V,
+) -> &'_ mut V
+{
+    match search(map, &key, |(k, _)| k) {
+        | PoloniusResult::Borrowing((_, value)) => value,
+        | PoloniusResult::Owned { value: idx, input_borrow: map } => {
+            map.insert(idx, (key, make()));
+            &mut map[idx].1
+        },
+    }
+}
+
+/// Fallible version of [`get_or_insert_sorted()`]: an error (or a [`None`])
+/// returned by `make` is propagated, and nothing gets inserted.
+///
+/// ```rust
+/// use ::polonius_the_crab::sorted::get_or_try_insert_sorted;
+///
+/// let mut ports = vec![("http", 80)];
+/// assert!(get_or_try_insert_sorted(&mut ports, "ssh", || "twenty-two".parse()).is_err());
+/// assert_eq!(get_or_try_insert_sorted(&mut ports, "ssh", || "22".parse()), Ok(&mut 22));
+/// assert_eq!(get_or_try_insert_sorted(&mut ports, "http", || None), Some(&mut 80));
+/// assert_eq!(ports, [("http", 80), ("ssh", 22)]);
+/// ```
+pub
+fn get_or_try_insert_sorted<K : Ord, V, R> (
+    map: &'_ mut Vec<(K, V)>,
+    key: K,
+    make: impl FnOnce() -> R,
+) -> <R::Residual as Residual>::WithOutput<&'_ mut V>
+where
+    R : Fallible<Output = V>,
+{
+    match search(map, &key, |(k, _)| k) {
+        | PoloniusResult::Borrowing((_, value)) => {
+            <R::Residual as Residual>::from_output(value)
+        },
+        | PoloniusResult::Owned { value: idx, input_borrow: map } => {
+            match Try::branch(make()) {
+                | Ok(value) => {
+                    map.insert(idx, (key, value));
+                    <R::Residual as Residual>::from_output(&mut map[idx].1)
+                },
+                | Err(residual) => Residual::with_output(residual),
+            }
+        },
+    }
+}
+
+/// Updates the value for `key` in the sorted `map` with `update`, or else
+/// inserts `insert()`; and returns the (updated or inserted) value.
+///
+/// ```rust
+/// use ::polonius_the_crab::sorted::upsert_sorted;
+///
+/// let mut scores = vec![("alice", 3)];
+/// assert_eq!(*upsert_sorted(&mut scores, "bob", || 1, |s| *s += 1), 1);
+/// assert_eq!(*upsert_sorted(&mut scores, "alice", || 1, |s| *s += 1), 4);
+/// assert_eq!(scores, [("alice", 4), ("bob", 1)]);
+/// ```
+pub
+fn upsert_sorted<K : Ord, V> (
+    map: &'_ mut Vec<(K, V)>,
+    key: K,
+    insert: impl FnOnce() -> V,
+    update: impl FnOnce(&mut V),
+) -> &'_ mut V
+{
+    match search(map, &key, |(k, _)| k) {
+        | PoloniusResult::Borrowing((_, value)) => {
+            update(value);
+            value
+        },
+        | PoloniusResult::Owned { value: idx, input_borrow: map } => {
+            map.insert(idx, (key, insert()));
+            &mut map[idx].1
+        },
+    }
+}
+
+/// Binary search: borrows the element found, or else yields the insertion
+/// index.
+fn search<'v, T, K : ?Sized + Ord> (
+    vec: &'v mut Vec<T>,
+    key: &'_ K,
+    mut key_of: impl FnMut(&T) -> &K,
+) -> PoloniusResult<&'v mut T, usize, &'v mut Vec<T>>
+{
+    polonius::<_, _, ForLt!(<'r> = &'r mut T)>(vec, |vec| {
+        match vec.binary_search_by(|elem| key_of(elem).cmp(key)) {
+            | Ok(idx) => PoloniusResult::Borrowing(&mut vec[idx]),
+            | Err(idx) => PoloniusResult::Owned(idx),
+        }
+    })
+}
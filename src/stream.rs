@@ -0,0 +1,261 @@
+//! Lending streams: asynchronous lending iterators, whose items may borrow
+//! from the stream itself (_e.g._, a frame decoded into an internal buffer).
+//!
+//! Adapters such as [`LendingStream::filter()`] have to poll the underlying
+//! stream again when an item is rejected, and otherwise return that item,
+//! borrowing from the stream: a conditional-return-of-a-borrow inside a
+//! `loop`, which does not compile without Polonius:
+//!
+/*!  - ```rust ,compile_fail
+    use {
+        ::core::task::{Context, Poll},
+        ::polonius_the_crab::stream::{Item, LendingStream},
+    };
+
+    fn poll_next_even<'s, S : LendingStream> (
+        stream: &'s mut S,
+        cx: &mut Context<'_>,
+        is_even: impl Fn(&Item<'_, S>) -> bool,
+    ) -> Poll<Option<Item<'s, S>>>
+    {
+        loop {
+            match stream.poll_next(cx) {
+                | Poll::Ready(Some(item)) if !is_even(&item) => continue,
+                | poll => return poll,
+            }
+        }
+    }
+    ``` */
+//!
+//! Hence this module's adapters, which use [`polonius_loop!`] to do so.
+//!
+//! ## Example
+//!
+/*!  - ```rust
+    use {
+        ::core::task::{Context, Poll},
+        ::polonius_the_crab::stream::{LendingStream, LendingStreamItem},
+        ::std::{sync::Arc, task::{Wake, Waker}},
+    };
+
+    /// Yields the lines of `text`, upper-cased into an internal buffer.
+    struct Shout {
+        lines: ::std::str::Lines<'static>,
+        buf: String,
+    }
+
+    impl<'next> LendingStreamItem<'next> for Shout {
+        type T = &'next str;
+    }
+
+    impl LendingStream for Shout {
+        fn poll_next (&'_ mut self, _: &'_ mut Context<'_>)
+          -> Poll<Option<&'_ str>>
+        {
+            Poll::Ready(self.lines.next().map(|line| {
+                self.buf = line.to_uppercase();
+                &self.buf[..]
+            }))
+        }
+    }
+
+    struct Noop;
+    impl Wake for Noop { fn wake (self: Arc<Self>) {} }
+    let waker = Waker::from(Arc::new(Noop));
+    let cx = &mut Context::from_waker(&waker);
+
+    let mut shout = Shout { lines: "hi\n\nho".lines(), buf: String::new() }
+        .filter(|line| !line.is_empty())
+    ;
+    assert_eq!(shout.poll_next(cx), Poll::Ready(Some("HI")));
+    assert_eq!(shout.poll_next(cx), Poll::Ready(Some("HO")));
+    assert_eq!(shout.poll_next(cx), Poll::Ready(None));
+    ``` */
+
+use {
+    ::core::{
+        future::Future,
+        pin::Pin,
+        task::{Context, Poll},
+    },
+    crate::{
+        polonius_break,
+        polonius_continue,
+        polonius_loop,
+        polonius_return,
+    },
+};
+
+/// The `T`ype of the items of a [`LendingStream`], when borrowed for `'next`.
+///
+/// The defaulted `Bound` parameter is not meant to be provided: it only
+/// introduces the implicit `Self : 'next` bound.
+pub
+trait LendingStreamItem<'next, Bound = &'next mut Self> {
+    type T;
+}
+
+/// Shorthand for the item of the `S` [`LendingStream`], when borrowed for
+/// `'next`.
+pub
+type Item<'next, S> = <S as LendingStreamItem<'next>>::T;
+
+/// An asynchronous lending iterator.
+///
+/// Unlike with `Stream`s, `poll_next()` takes a plain `&mut self`: a
+/// self-referential lending stream would not be able to lend its items
+/// anyways.
+pub
+trait LendingStream : for<'next> LendingStreamItem<'next> {
+    /// Attempts to pull out the next item of this stream, registering the
+    /// current task for wakeup if it is not yet available.
+    ///
+    /// `Poll::Ready(None)` means that the stream is exhausted.
+    fn poll_next (&'_ mut self, cx: &'_ mut Context<'_>)
+      -> Poll<Option<Item<'_, Self>>>
+    ;
+
+    /// Only yields the items for which `predicate` returns `true`.
+    fn filter<P> (self, predicate: P)
+      -> Filter<Self, P>
+    where
+        Self : Sized,
+        P : FnMut(&Item<'_, Self>) -> bool,
+    {
+        Filter { stream: self, predicate }
+    }
+
+    /// Yields the items up until (and excluding) the first one for which
+    /// `predicate` returns `false`.
+    fn take_while<P> (self, predicate: P)
+      -> TakeWhile<Self, P>
+    where
+        Self : Sized,
+        P : FnMut(&Item<'_, Self>) -> bool,
+    {
+        TakeWhile { stream: self, predicate, done: false }
+    }
+
+    /// Future resolving to the first item for which `predicate` returns
+    /// `true`, still borrowing from this stream.
+    fn find<P> (&'_ mut self, predicate: P)
+      -> Find<'_, Self, P>
+    where
+        P : FnMut(&Item<'_, Self>) -> bool,
+    {
+        Find { stream: Some(self), predicate }
+    }
+}
+
+/// See [`LendingStream::filter()`].
+pub
+struct Filter<S, P> {
+    stream: S,
+    predicate: P,
+}
+
+impl<'next, S, P> LendingStreamItem<'next> for Filter<S, P>
+where
+    S : LendingStream,
+    P : FnMut(&Item<'_, S>) -> bool,
+{
+    type T = Item<'next, S>;
+}
+
+impl<S, P> LendingStream for Filter<S, P>
+where
+    S : LendingStream,
+    P : FnMut(&Item<'_, S>) -> bool,
+{
+    fn poll_next (&'_ mut self, cx: &'_ mut Context<'_>)
+      -> Poll<Option<Item<'_, Self>>>
+    {
+        let mut stream = &mut self.stream;
+        polonius_loop!(|stream| -> Poll<Option<Item<'polonius, Self>>> {
+            match stream.poll_next(cx) {
+                | Poll::Ready(Some(item)) if !(self.predicate)(&item) => {
+                    polonius_continue!();
+                },
+                | poll => polonius_return!(poll),
+            }
+        })
+    }
+}
+
+/// See [`LendingStream::take_while()`].
+pub
+struct TakeWhile<S, P> {
+    stream: S,
+    predicate: P,
+    done: bool,
+}
+
+impl<'next, S, P> LendingStreamItem<'next> for TakeWhile<S, P>
+where
+    S : LendingStream,
+    P : FnMut(&Item<'_, S>) -> bool,
+{
+    type T = Item<'next, S>;
+}
+
+impl<S, P> LendingStream for TakeWhile<S, P>
+where
+    S : LendingStream,
+    P : FnMut(&Item<'_, S>) -> bool,
+{
+    fn poll_next (&'_ mut self, cx: &'_ mut Context<'_>)
+      -> Poll<Option<Item<'_, Self>>>
+    {
+        if self.done {
+            return Poll::Ready(None);
+        }
+        // No Polonius needed here: the rejected `item` is dropped before
+        // touching `self.done`, a field disjoint from `self.stream`.
+        match self.stream.poll_next(cx) {
+            | Poll::Ready(Some(item)) if !(self.predicate)(&item) => {
+                self.done = true;
+                Poll::Ready(None)
+            },
+            | poll => poll,
+        }
+    }
+}
+
+/// See [`LendingStream::find()`].
+pub
+struct Find<'stream, S : ?Sized, P> {
+    /// `None` once completed.
+    stream: Option<&'stream mut S>,
+    predicate: P,
+}
+
+// `predicate` is never pinned.
+impl<S : ?Sized, P> Unpin for Find<'_, S, P> {}
+
+impl<'stream, S, P> Future for Find<'stream, S, P>
+where
+    S : ?Sized + LendingStream,
+    P : FnMut(&Item<'_, S>) -> bool,
+{
+    type Output = Option<Item<'stream, S>>;
+
+    fn poll (self: Pin<&'_ mut Self>, cx: &'_ mut Context<'_>)
+      -> Poll<Self::Output>
+    {
+        let this = self.get_mut();
+        let mut stream = this.stream.take().expect("`Find` polled after completion");
+        let predicate = &mut this.predicate;
+        polonius_loop!(|stream| -> Poll<Option<Item<'polonius, S>>> {
+            match stream.poll_next(cx) {
+                | Poll::Ready(Some(item)) if !predicate(&item) => {
+                    polonius_continue!();
+                },
+                | Poll::Pending => polonius_break!(),
+                | poll => polonius_return!(poll),
+            }
+        });
+        // `Pending`: we got the `stream` borrow back, to be polled again.
+        this.stream = Some(stream);
+        Poll::Pending
+    }
+}
@@ -0,0 +1,152 @@
+//! Searching n-ary trees (ASTs, DOM-like structures, …) for a node, and
+//! returning a `&mut` borrow to it.
+//!
+//! The tree shape is described by a `children` accessor, _e.g._,
+//! `|node: &mut Node| &mut node.children[..]`.
+//!
+//! The natural implementation of such a search does not compile without
+//! Polonius, since "trying" a node with `f` and, on failure, going through its
+//! `children` is yet another conditional-return-of-a-borrow:
+//!
+/*!  - ```rust ,compile_fail
+    fn find_map_mut<'r, N, T> (
+        node: &'r mut N,
+        children: &mut impl FnMut(&mut N) -> &mut [N],
+        f: &mut impl FnMut(&mut N) -> Option<&mut T>,
+    ) -> Option<&'r mut T>
+    {
+        if let Some(found) = f(node) {
+            return Some(found);
+        }
+        children(node).iter_mut().find_map(|child| find_map_mut(child, children, f))
+    }
+    ``` */
+//!
+//! ## Example
+//!
+/*!  - ```rust
+    use ::polonius_the_crab::tree;
+
+    struct Node {
+        name: &'static str,
+        children: Vec<Node>,
+    }
+
+    let leaf = |name| Node { name, children: vec![] };
+    let mut root = Node {
+        name: "root",
+        children: vec![
+            Node { name: "a", children: vec![leaf("target")] },
+            leaf("target"),
+        ],
+    };
+
+    // depth-first: the nested `target` is found first.
+    let found = tree::find_mut_dfs(
+        &mut root,
+        |node| &mut node.children[..],
+        |node| node.name == "target",
+    ).unwrap();
+    found.children.push(leaf("found via DFS"));
+    assert_eq!(root.children[0].children[0].children.len(), 1);
+    ``` */
+
+use crate::{
+    ForLt,
+    polonius,
+    PoloniusResult,
+};
+
+/// Depth-first (pre-order) search for the first node satisfying `pred`.
+pub
+fn find_mut_dfs<N> (
+    root: &'_ mut N,
+    children: impl FnMut(&mut N) -> &mut [N],
+    mut pred: impl FnMut(&N) -> bool,
+) -> Option<&'_ mut N>
+{
+    find_map_mut_dfs(root, children, |node| if pred(node) {
+        Some(node)
+    } else {
+        None
+    })
+}
+
+/// Depth-first (pre-order) search for the first node for which `f` returns
+/// a borrow (of the node, or of a part of it).
+pub
+fn find_map_mut_dfs<N, T : ?Sized> (
+    root: &'_ mut N,
+    mut children: impl FnMut(&mut N) -> &mut [N],
+    mut f: impl FnMut(&mut N) -> Option<&mut T>,
+) -> Option<&'_ mut T>
+{
+    return dfs(root, &mut children, &mut f);
+    // where
+    fn dfs<'r, N, T : ?Sized> (
+        node: &'r mut N,
+        children: &mut impl FnMut(&mut N) -> &mut [N],
+        f: &mut impl FnMut(&mut N) -> Option<&mut T>,
+    ) -> Option<&'r mut T>
+    {
+        match try_node(node, f) {
+            | PoloniusResult::Borrowing(found) => Some(found),
+            | PoloniusResult::Owned { input_borrow: node, .. } => {
+                children(node)
+                    .iter_mut()
+                    .find_map(|child| dfs(child, children, f))
+            },
+        }
+    }
+}
+
+/// Breadth-first search for the first node satisfying `pred`: among the
+/// matching nodes, the one closest to the `root` is returned.
+#[cfg(feature = "alloc")]
+pub
+fn find_mut_bfs<N> (
+    root: &'_ mut N,
+    children: impl FnMut(&mut N) -> &mut [N],
+    mut pred: impl FnMut(&N) -> bool,
+) -> Option<&'_ mut N>
+{
+    find_map_mut_bfs(root, children, |node| if pred(node) {
+        Some(node)
+    } else {
+        None
+    })
+}
+
+/// Breadth-first search for the first node for which `f` returns a borrow
+/// (of the node, or of a part of it).
+#[cfg(feature = "alloc")]
+pub
+fn find_map_mut_bfs<N, T : ?Sized> (
+    root: &'_ mut N,
+    mut children: impl FnMut(&mut N) -> &mut [N],
+    mut f: impl FnMut(&mut N) -> Option<&mut T>,
+) -> Option<&'_ mut T>
+{
+    let mut queue = ::alloc::collections::VecDeque::new();
+    queue.push_back(root);
+    while let Some(node) = queue.pop_front() {
+        match try_node(node, &mut f) {
+            | PoloniusResult::Borrowing(found) => return Some(found),
+            | PoloniusResult::Owned { input_borrow: node, .. } => {
+                queue.extend(children(node));
+            },
+        }
+    }
+    None
+}
+
+fn try_node<'r, N, T : ?Sized> (
+    node: &'r mut N,
+    f: &mut impl FnMut(&mut N) -> Option<&mut T>,
+) -> PoloniusResult<&'r mut T, (), &'r mut N>
+{
+    polonius::<_, _, ForLt!(<'n> = &'n mut T)>(node, |node| match f(node) {
+        | Some(found) => PoloniusResult::Borrowing(found),
+        | None => PoloniusResult::Owned(()),
+    })
+}
@@ -1,5 +1,11 @@
-use never_say_never::Never as ǃ;
+pub(crate) use never_say_never::Never as ǃ;
 
+/// Polyfill of the `?`-operator machinery, used by [`polonius_try!`] and by
+/// the `try`-flavored helpers of this crate.
+///
+/// Only [`Result`] and [`Option`] implement it.
+///
+/// [`polonius_try!`]: crate::polonius_try!
 pub
 trait Try<Residual> : Sized {
     type Output;
@@ -9,6 +15,7 @@ trait Try<Residual> : Sized {
     ;
 }
 
+/// The failure half of a [`Try`] type: `Result<!, E>` or `Option<!>`.
 // Trait defined in this direction to hopefully minimize type inference errors.
 pub
 trait Residual {
@@ -17,6 +24,20 @@ trait Residual {
     fn with_output<T>(this: Self)
       -> Self::WithOutput<T>
     ;
+
+    fn from_output<T>(output: T)
+      -> Self::WithOutput<T>
+    ;
+}
+
+/// A [`Try`] type along with its own [`Residual`], _i.e._, the one which
+/// propagates its failure as is (no `From` conversion of the error).
+///
+/// Used by the APIs lacking a return type annotation from which to infer
+/// the [`Residual`] to use.
+pub
+trait Fallible : Try<Self::Residual> {
+    type Residual : Residual;
 }
 
 impl<Ok, Err, E> Try<Result<ǃ, Err>> for Result<Ok, E>
@@ -42,6 +63,17 @@ impl<Err> Residual for Result<ǃ, Err> {
     {
         can_it_be_this_simple?
     }
+
+    #[inline]
+    fn from_output<Ok>(output: Ok)
+      -> Result<Ok, Err>
+    {
+        Ok(output)
+    }
+}
+
+impl<Ok, E> Fallible for Result<Ok, E> {
+    type Residual = Result<ǃ, E>;
 }
 
 type None = Option<ǃ>;
@@ -66,6 +98,56 @@ impl Residual for None {
     {
         can_it_be_this_simple?
     }
+
+    #[inline]
+    fn from_output<T>(output: T)
+      -> Option<T>
+    {
+        Some(output)
+    }
+}
+
+impl<T> Fallible for Option<T> {
+    type Residual = None;
+}
+
+/// With the `nightly-try` feature, a plain `?` within a [`polonius!`] or
+/// [`polonius_loop!`] body behaves like a genuine one: the (closure-based)
+/// bodies return these `PoloniusResult`s, which thus become able to absorb the
+/// residuals of `?`, converted into the return type of the function, which
+/// the expansion then `return`s.
+///
+/// This includes the labeled `polonius!('label, …)` flavor, matching the
+/// straight-line bodies of the `polonius` Cargo feature: use [`polonius_try!`]
+/// to `break` out of `'label` instead.
+///
+/// [`polonius!`]: crate::polonius!
+/// [`polonius_loop!`]: crate::polonius_loop!
+/// [`polonius_try!`]: crate::polonius_try!
+#[cfg(feature = "nightly-try")]
+mod try_operator {
+    use {
+        ::core::ops::FromResidual,
+        crate::{
+            ඞ::Dependent,
+            PoloniusResult,
+        },
+    };
+
+    impl<R, Return, Break, Propagated, Owned> FromResidual<R>
+        for PoloniusResult<Dependent<Return, Break, Propagated>, Owned>
+    where
+        Propagated : FromResidual<R>,
+    {
+        #[inline]
+        fn from_residual (residual: R)
+          -> Self
+        {
+            PoloniusResult::Borrowing(
+                Dependent::Propagate(Propagated::from_residual(residual))
+            )
+        }
+    }
 }
 
 /// On 1.67.0 we get a weird interaction with the `WithOutput<T>` GAT when this
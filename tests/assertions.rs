@@ -0,0 +1,88 @@
+#![forbid(unsafe_code)]
+
+use ::polonius_the_crab::{
+    assert_borrowing,
+    assert_owned,
+    polonius,
+    ForLt,
+    PoloniusResult,
+};
+
+/// Tentatively borrows the first element of `v`, if any; else returns its
+/// (`0`) length.
+fn first (v: &'_ mut Vec<String>)
+  -> PoloniusResult<&'_ mut String, usize, &'_ mut Vec<String>>
+{
+    polonius::<_, _, ForLt!(<'r> = &'r mut String)>(v, |v| {
+        let len = v.len();
+        match v.first_mut() {
+            | Some(first) => PoloniusResult::Borrowing(first),
+            | None => PoloniusResult::Owned(len),
+        }
+    })
+}
+
+#[test]
+fn borrowing ()
+{
+    let mut v = vec!["hello".to_owned()];
+    assert_borrowing!(first(&mut v)).push(',');
+    assert_borrowing!(first(&mut v), s if s.ends_with(',')).push_str(" world");
+    assert_eq!(v, ["hello, world"]);
+}
+
+#[test]
+fn owned ()
+{
+    let mut v = vec![];
+    assert_owned!(first(&mut v)).push("a".into());
+    v.clear();
+    assert_owned!(first(&mut v), 0 | 1).push("b".into());
+    v.clear();
+    assert_owned!(first(&mut v), n if *n == 0).push("c".into());
+    assert_eq!(v, ["c"]);
+}
+
+#[test]
+#[should_panic(expected = "\
+    assertion failed: `first(&mut v)` is `PoloniusResult::Owned { … }`, \
+    expected `PoloniusResult::Borrowing(…)`\
+")]
+fn borrowing_wrong_variant ()
+{
+    let mut v = vec![];
+    assert_borrowing!(first(&mut v));
+}
+
+#[test]
+#[should_panic(expected = "\
+    assertion failed: `first(&mut v)` is `PoloniusResult::Borrowing(…)`, \
+    but its dependent value does not match `s if s.is_empty()`\
+")]
+fn borrowing_wrong_pattern ()
+{
+    let mut v = vec!["not empty".to_owned()];
+    assert_borrowing!(first(&mut v), s if s.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "\
+    assertion failed: `first(&mut v)` is `PoloniusResult::Borrowing(…)`, \
+    expected `PoloniusResult::Owned { … }`\
+")]
+fn owned_wrong_variant ()
+{
+    let mut v = vec![String::new()];
+    assert_owned!(first(&mut v));
+}
+
+#[test]
+#[should_panic(expected = "\
+    assertion failed: `first(&mut v)` is `PoloniusResult::Owned { … }`, \
+    but its `value` does not match `1 | 2`\
+")]
+fn owned_wrong_pattern ()
+{
+    let mut v = vec![];
+    assert_owned!(first(&mut v), 1 | 2);
+}
@@ -0,0 +1,148 @@
+#![forbid(unsafe_code)]
+
+use {
+    ::polonius_the_crab::{
+        branch_fn,
+        polonius_with,
+        ForLt,
+        PoloniusBranch,
+        PoloniusResult,
+    },
+    ::std::collections::HashMap,
+};
+
+type Map = HashMap<u8, String>;
+
+type StrMut = ForLt!(<'r> = &'r mut String);
+
+/// The value at the given key.
+#[derive(Clone, Copy)]
+struct ByKey(u8);
+
+/// The first value (by key) matching the given length, if any, or else the
+/// number of values.
+#[derive(Clone, Copy)]
+struct ByLen(usize);
+
+impl PoloniusBranch<Map> for ByKey {
+    type Borrowing = StrMut;
+    type Owned = usize;
+
+    fn branch (self, map: &'_ mut Map)
+      -> PoloniusResult<&'_ mut String, usize>
+    {
+        let len = map.len();
+        match map.get_mut(&self.0) {
+            | Some(v) => PoloniusResult::Borrowing(v),
+            | None => PoloniusResult::Owned(len),
+        }
+    }
+}
+
+impl PoloniusBranch<Map> for ByLen {
+    type Borrowing = StrMut;
+    type Owned = usize;
+
+    fn branch (self, map: &'_ mut Map)
+      -> PoloniusResult<&'_ mut String, usize>
+    {
+        let len = map.len();
+        let mut matching: Vec<_> = map.iter_mut().filter(|(_, v)| v.len() == self.0).collect();
+        matching.sort_by_key(|&(&k, _)| k);
+        match matching.into_iter().next() {
+            | Some((_, v)) => PoloniusResult::Borrowing(v),
+            | None => PoloniusResult::Owned(len),
+        }
+    }
+}
+
+/// Generic over the lookup strategy.
+fn get_or_insert<S> (map: &'_ mut Map, strategy: S, key: u8)
+  -> &'_ mut String
+where
+    S : PoloniusBranch<Map, Borrowing = StrMut, Owned = usize>,
+{
+    match polonius_with(map, strategy) {
+        | PoloniusResult::Borrowing(v) => v,
+        | PoloniusResult::Owned { value: len, input_borrow: map } => {
+            map.entry(key).or_insert_with(|| format!("#{len}"))
+        },
+    }
+}
+
+fn map ()
+  -> Map
+{
+    Map::from([(0, "zero".into()), (1, "one".into()), (2, "two".into())])
+}
+
+#[test]
+fn strategies ()
+{
+    let mut map = map();
+    get_or_insert(&mut map, ByKey(1), 9).push('!');
+    get_or_insert(&mut map, ByLen(3), 9).push('?');
+    assert_eq!(map, Map::from([(0, "zero".into()), (1, "one!".into()), (2, "two?".into())]));
+    assert_eq!(get_or_insert(&mut map, ByKey(7), 7), "#3");
+    assert_eq!(get_or_insert(&mut map, ByLen(42), 8), "#4");
+    assert_eq!(map.len(), 5);
+}
+
+#[test]
+fn stored_in_a_field ()
+{
+    struct Cache<S> {
+        map: Map,
+        strategy: S,
+    }
+
+    impl<S> Cache<S>
+    where
+        S : Copy + PoloniusBranch<Map, Borrowing = StrMut, Owned = usize>,
+    {
+        fn get (&mut self, key: u8)
+          -> &mut String
+        {
+            get_or_insert(&mut self.map, self.strategy, key)
+        }
+    }
+
+    let mut cache = Cache { map: map(), strategy: ByLen(4) };
+    cache.get(0).push('!');
+    assert_eq!(cache.get(0), "zero!");
+    assert_eq!(cache.get(5), "#3");
+    assert_eq!(cache.get(5), "#3");
+    assert_eq!(cache.map.len(), 4);
+}
+
+#[test]
+fn closures ()
+{
+    let key = 2;
+    let by_key = branch_fn::<StrMut, _, _, _>(|map: &mut Map| {
+        let len = map.len();
+        match map.get_mut(&key) {
+            | Some(v) => PoloniusResult::Borrowing(v),
+            | None => PoloniusResult::Owned(len),
+        }
+    });
+    let mut map = map();
+    get_or_insert(&mut map, by_key, key).push('!');
+    assert_eq!(map[&2], "two!");
+
+    // `Owned` output, with a moved-in capture.
+    let name = String::from("three");
+    let not_found = branch_fn::<StrMut, _, _, _>(move |map: &mut Map| {
+        match map.values_mut().find(|v| **v == name) {
+            | Some(v) => PoloniusResult::Borrowing(v),
+            | None => PoloniusResult::Owned(name),
+        }
+    });
+    match polonius_with(&mut map, not_found) {
+        | PoloniusResult::Borrowing(_) => unreachable!(),
+        | PoloniusResult::Owned { value: name, input_borrow: map } => {
+            map.insert(3, name);
+        },
+    }
+    assert_eq!(map[&3], "three");
+}
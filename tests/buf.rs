@@ -0,0 +1,132 @@
+#![forbid(unsafe_code)]
+
+use {
+    ::polonius_the_crab::buf::{BufferedSource, IoSource},
+    ::std::io::{self, Read},
+};
+
+/// In-memory reader yielding at most `chunk` bytes per `read`, so as to
+/// exercise frames straddling several reads.
+struct Chunked<'data> {
+    data: &'data [u8],
+    chunk: usize,
+}
+
+impl Read for Chunked<'_> {
+    fn read (&mut self, buf: &mut [u8])
+      -> io::Result<usize>
+    {
+        let n = self.chunk.min(buf.len()).min(self.data.len());
+        buf[.. n].copy_from_slice(&self.data[.. n]);
+        self.data = &self.data[n ..];
+        Ok(n)
+    }
+}
+
+/// Errors out once the data is exhausted.
+struct Failing<'data>(&'data [u8]);
+
+impl Read for Failing<'_> {
+    fn read (&mut self, buf: &mut [u8])
+      -> io::Result<usize>
+    {
+        if self.0.is_empty() {
+            return Err(io::ErrorKind::ConnectionReset.into());
+        }
+        self.0.read(buf)
+    }
+}
+
+/// `u16` big-endian length prefix, followed by the payload.
+fn length_prefixed (buffered: &[u8]) -> Option<usize> {
+    let len = match *buffered {
+        | [hi, lo, ..] => 2 + usize::from(u16::from_be_bytes([hi, lo])),
+        | _ => return None,
+    };
+    (buffered.len() >= len).then_some(len)
+}
+
+fn next_payload<S : BufferedSource> (source: &'_ mut S)
+  -> Result<Option<&'_ [u8]>, S::Error>
+{
+    Ok(source.read_frame(length_prefixed)?.map(|frame| &frame[2 ..]))
+}
+
+#[test]
+fn frames_across_reads ()
+{
+    let payloads: &[&[u8]] = &[b"hello", b"", &[42; 300], b"bye"];
+    let mut data = vec![];
+    for payload in payloads {
+        data.extend((payload.len() as u16).to_be_bytes());
+        data.extend(*payload);
+    }
+    for chunk in [1, 2, 7, 1024] {
+        let mut source = IoSource::with_chunk_size(chunk, Chunked { data: &data, chunk });
+        for &expected in payloads {
+            let payload = next_payload(&mut source).unwrap().unwrap();
+            assert_eq!(payload, expected);
+            source.consume(2 + expected.len());
+        }
+        assert!(next_payload(&mut source).unwrap().is_none());
+        assert!(source.buffered().is_empty());
+    }
+}
+
+#[test]
+fn incomplete_frame_at_eof ()
+{
+    let mut source = IoSource::with_chunk_size(3, Chunked { data: b"\0\x05abc", chunk: 2 });
+    assert!(next_payload(&mut source).unwrap().is_none());
+    assert_eq!(source.buffered(), b"\0\x05abc");
+}
+
+#[test]
+fn read_error_is_propagated ()
+{
+    let mut source = IoSource::new(Failing(b"\0\x05ab"));
+    let err = next_payload(&mut source).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::ConnectionReset);
+    // nothing was lost.
+    assert_eq!(source.buffered(), b"\0\x05ab");
+}
+
+#[test]
+#[should_panic = "`parse` returned a frame length of 5, but was only given 3 bytes"]
+fn frame_length_past_the_buffered_bytes ()
+{
+    let mut source: &[u8] = b"abc";
+    let _ = source.read_frame(|_| Some(5));
+}
+
+#[test]
+fn lines ()
+{
+    let text = b"first line\n\nthird line, a longer one\nno trailing newline";
+    for chunk in [1, 4, 100] {
+        let mut source = IoSource::with_chunk_size(chunk, Chunked { data: text, chunk });
+        let mut lines = vec![];
+        while let Some(line) = source.read_line_borrowed().unwrap() {
+            lines.push(String::from_utf8(line.to_vec()).unwrap());
+            let len = line.len();
+            source.consume(len);
+        }
+        assert_eq!(lines, [
+            "first line\n",
+            "\n",
+            "third line, a longer one\n",
+            "no trailing newline",
+        ]);
+    }
+}
+
+#[test]
+fn in_memory_lines ()
+{
+    let mut source: &[u8] = b"a\nb";
+    assert_eq!(source.read_line_borrowed(), Ok(Some(&b"a\n"[..])));
+    source.consume(2);
+    assert_eq!(source.read_line_borrowed(), Ok(Some(&b"b"[..])));
+    source.consume(1);
+    assert_eq!(source.read_line_borrowed(), Ok(None));
+}
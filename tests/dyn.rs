@@ -0,0 +1,161 @@
+use {
+    ::polonius_the_crab::prelude::*,
+    ::std::collections::HashMap,
+};
+
+fn get_or_insert (mut map: &'_ mut HashMap<i32, String>)
+  -> &'_ String
+{
+    polonius!(dyn |map| -> &'polonius String {
+        if let Some(v) = map.get(&22) {
+            polonius_return!(v);
+        }
+    });
+    map.insert(22, "twenty-two".into());
+    &map[&22]
+}
+
+fn get_or_try_insert (mut map: &'_ mut HashMap<i32, String>, fail: bool)
+  -> Result<&'_ String, ()>
+{
+    let value = polonius!(dyn |map| -> Result<&'polonius String, ()> {
+        if let Some(v) = map.get(&22) {
+            polonius_return!(Ok(v));
+        }
+        if fail {
+            polonius_try!(Err(()));
+        }
+        exit_polonius!(42);
+    });
+    map.insert(22, value.to_string());
+    Ok(&map[&22])
+}
+
+fn first_even (mut v: &'_ mut Vec<i32>)
+  -> &'_ mut i32
+{
+    let mut i = 0;
+    polonius_loop!(dyn |v| -> &'polonius mut i32 {
+        match v.get_mut(i) {
+            | Some(x) if *x % 2 == 0 => polonius_return!(x),
+            | Some(_) => i += 1,
+            | None => polonius_break!(),
+        }
+    });
+    v.push(0);
+    v.last_mut().unwrap()
+}
+
+fn first_even_or_len (mut v: &'_ mut Vec<i32>)
+  -> Result<&'_ mut i32, usize>
+{
+    let mut i = 0;
+    let found = polonius_loop!(dyn |v| -> Result<&'polonius mut i32, usize>, break: &'polonius mut i32 {
+        match v.get_mut(i) {
+            | Some(x) if *x % 2 == 0 => polonius_break_dependent!(x),
+            | Some(_) => i += 1,
+            | None => polonius_return!(Err(i)),
+        }
+    });
+    *found += 1;
+    Ok(found)
+}
+
+#[test]
+fn dyn_polonius ()
+{
+    let mut map = HashMap::new();
+    assert_eq!(get_or_insert(&mut map), "twenty-two");
+    assert_eq!(get_or_try_insert(&mut map, true), Ok(&"twenty-two".into()));
+    map.clear();
+    assert_eq!(get_or_try_insert(&mut map, true), Err(()));
+    assert_eq!(get_or_try_insert(&mut map, false), Ok(&"42".into()));
+}
+
+#[test]
+fn dyn_polonius_loop ()
+{
+    let mut v = vec![1, 3, 4, 5];
+    *first_even(&mut v) = 40;
+    assert_eq!(v, [1, 3, 40, 5]);
+    *first_even(&mut vec![1]) += 1;
+    assert_eq!(first_even_or_len(&mut v), Ok(&mut 41));
+    assert_eq!(first_even_or_len(&mut vec![1, 3]), Err(2));
+}
+
+/// Compares the build time and code size of many `polonius!` call sites with
+/// and without `dyn`.
+///
+/// `cargo test --release --test dyn -- --ignored --nocapture`
+#[test]
+#[ignore]
+fn bench_dyn_vs_generic ()
+{
+    const CALL_SITES: usize = 500;
+
+    let root = ::std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("bench_dyn");
+    for dyn_ in ["", "dyn"] {
+        let name = if dyn_.is_empty() { "generic" } else { "dyn" };
+        let dir = root.join(name);
+        ::std::fs::create_dir_all(dir.join("src")).unwrap();
+        ::std::fs::write(dir.join("Cargo.toml"), format!(r#"
+            [package]
+            name = "bench-{name}"
+            version = "0.0.0"
+            edition = "2021"
+
+            [dependencies]
+            polonius-the-crab.path = {manifest_dir:?}
+
+            [workspace]
+        "#, manifest_dir = env!("CARGO_MANIFEST_DIR"))).unwrap();
+        let mut main = String::from("\
+            use {::polonius_the_crab::prelude::*, ::std::collections::HashMap};\n\
+            fn main () {\n\
+                let mut map = HashMap::<u32, String>::new();\n\
+                let map = &mut map;\n\
+                let mut total = 0;\n\
+        ");
+        for i in 0 .. CALL_SITES {
+            main += &format!("total += f{i}(map).len();\n");
+        }
+        main += "println!(\"{total}\");\n}\n";
+        for i in 0 .. CALL_SITES {
+            main += &format!("
+                fn f{i} (mut map: &'_ mut HashMap<u32, String>)
+                  -> &'_ String
+                {{
+                    polonius!({dyn_} |map| -> &'polonius String {{
+                        if let Some(v) = map.get(&{i}) {{
+                            polonius_return!(v);
+                        }}
+                    }});
+                    map.insert({i}, {i}.to_string());
+                    &map[&{i}]
+                }}
+            ");
+        }
+        ::std::fs::write(dir.join("src/main.rs"), main).unwrap();
+
+        let cargo = |args: &[&str]| {
+            let status =
+                ::std::process::Command::new(env!("CARGO"))
+                    .current_dir(&dir)
+                    .args(args)
+                    .status()
+                    .unwrap()
+            ;
+            assert!(status.success());
+        };
+        // Build (and cache) the dependencies first, then only invalidate the
+        // bench crate itself, so as to time the `polonius!` call sites alone.
+        cargo(&["build", "--release", "--quiet"]);
+        cargo(&["clean", "--release", "--quiet", "-p", &format!("bench-{name}")]);
+        let start = ::std::time::Instant::now();
+        cargo(&["build", "--release", "--quiet"]);
+        let elapsed = start.elapsed();
+        let bin = dir.join("target/release").join(format!("bench-{name}"));
+        let size = ::std::fs::metadata(bin).unwrap().len();
+        println!("{name:>7}: {CALL_SITES} call sites built in {elapsed:?}, binary of {size} bytes");
+    }
+}
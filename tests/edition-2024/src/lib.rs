@@ -0,0 +1,11 @@
+//! Edition 2024 caller of the `polonius_the_crab` macros.
+//!
+//! The macros are expanded with the edition of the _caller_ for the tokens it
+//! provides (tail expression temporary scopes, `if let` rescoping, never type
+//! fallback), hence this separate crate: see `tests/macros.rs`.
+//!
+//! Run with `cargo test` from within this directory (whose
+//! `rust-toolchain.toml` picks `stable`), or, from the root of the repository
+//! (whose `rust-toolchain.toml` pins the MSRV, predating edition 2024), with
+//! `cargo +stable test --manifest-path tests/edition-2024/Cargo.toml`.
+#![forbid(unsafe_code)]
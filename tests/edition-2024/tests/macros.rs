@@ -0,0 +1,351 @@
+#![forbid(unsafe_code)]
+
+use {
+    ::polonius_the_crab::{
+        assert_borrowing,
+        assert_owned,
+        polonius_alt,
+        polonius_break,
+        polonius_break_dependent,
+        polonius_continue,
+        polonius_try,
+        prelude::*,
+        ForLt,
+        PoloniusResult,
+    },
+    ::std::{
+        cell::RefCell,
+        collections::HashMap,
+    },
+};
+
+fn map ()
+  -> HashMap<u8, String>
+{
+    HashMap::from([(0, "zero".into()), (1, "one".into())])
+}
+
+#[test]
+fn polonius_return ()
+{
+    fn get_or_insert (mut map: &'_ mut HashMap<u8, String>, key: u8)
+      -> &'_ String
+    {
+        polonius!(|map| -> &'polonius String {
+            if let Some(v) = map.get(&key) {
+                polonius_return!(v);
+            }
+        });
+        map.insert(key, "new".into());
+        &map[&key]
+    }
+
+    let mut map = map();
+    assert_eq!(get_or_insert(&mut map, 1), "one");
+    assert_eq!(get_or_insert(&mut map, 2), "new");
+}
+
+#[test]
+fn if_let_else_rescoping ()
+{
+    // Edition 2024 drops the scrutinee temporaries before the `else` branch.
+    fn get_or_len (mut map: &'_ mut HashMap<u8, String>, key: u8)
+      -> Result<&'_ String, usize>
+    {
+        let len = polonius!(|map| -> Result<&'polonius String, usize> {
+            if let Some(v) = map.get(&key) {
+                polonius_return!(Ok(v));
+            } else {
+                map.len()
+            }
+        });
+        map.clear();
+        Err(len)
+    }
+
+    let mut map = map();
+    assert_eq!(get_or_len(&mut map, 0), Ok(&"zero".to_owned()));
+    assert_eq!(get_or_len(&mut map, 2), Err(2));
+    assert!(map.is_empty());
+}
+
+#[test]
+fn tail_expression_temporaries ()
+{
+    // Edition 2024 drops the temporaries of a block's tail expression before
+    // its locals: `cell` may be borrowed in the tail expression of the body.
+    fn get_or_len (mut map: &'_ mut HashMap<u8, String>)
+      -> Result<&'_ String, usize>
+    {
+        let len = polonius!(|map| -> Result<&'polonius String, usize> {
+            if let Some(v) = map.get(&42) {
+                polonius_return!(Ok(v));
+            }
+            let cell = RefCell::new(map.len());
+            *cell.borrow()
+        });
+        map.clear();
+        Err(len)
+    }
+
+    assert_eq!(get_or_len(&mut map()), Err(2));
+}
+
+#[test]
+fn diverging_body ()
+{
+    // Edition 2024 changes the never type fallback from `()` to `!`.
+    fn get (mut map: &'_ mut HashMap<u8, String>)
+      -> &'_ String
+    {
+        polonius!(|map| -> &'polonius String {
+            polonius_return!(&map[&0]);
+        });
+        unreachable!()
+    }
+
+    fn get_loop (mut map: &'_ mut HashMap<u8, String>)
+      -> &'_ String
+    {
+        polonius_loop!(|map| -> &'polonius String {
+            polonius_return!(&map[&1]);
+        })
+    }
+
+    assert_eq!(get(&mut map()), "zero");
+    assert_eq!(get_loop(&mut map()), "one");
+}
+
+#[test]
+fn exit_polonius ()
+{
+    fn classify (mut map: &'_ mut HashMap<u8, String>, key: u8)
+      -> &'_ str
+    {
+        let found = polonius!(|map| -> &'polonius str {
+            if map.is_empty() {
+                exit_polonius!(false);
+            }
+            if let Some(v) = map.get(&key) {
+                if v.len() > 3 {
+                    polonius_return!(v);
+                }
+                exit_polonius!(true);
+            }
+            false
+        });
+        assert_eq!(found, map.contains_key(&key));
+        map.entry(key).or_insert_with(|| "?".into())
+    }
+
+    let mut map = map();
+    assert_eq!(classify(&mut map, 0), "zero");
+    assert_eq!(classify(&mut map, 1), "one");
+    assert_eq!(classify(&mut map, 7), "?");
+    assert_eq!(classify(&mut HashMap::new(), 7), "?");
+}
+
+#[test]
+fn polonius_try ()
+{
+    fn parse (mut map: &'_ mut HashMap<u8, String>, key: u8)
+      -> Result<&'_ str, ::core::num::ParseIntError>
+    {
+        polonius!(|map| -> Result<&'polonius str, ::core::num::ParseIntError> {
+            if let Some(v) = map.get(&key) {
+                let _: u8 = polonius_try!(v.parse());
+                polonius_return!(Ok(v));
+            }
+        });
+        Ok(map.entry(key).or_insert_with(|| key.to_string()))
+    }
+
+    fn first_char (mut map: &'_ mut HashMap<u8, String>, key: u8)
+      -> Option<&'_ str>
+    {
+        polonius!(|map| -> Option<&'polonius str> {
+            if let Some(v) = map.get(&key) {
+                let c = polonius_try!(v.chars().next());
+                polonius_return!(Some(&v[.. c.len_utf8()]));
+            }
+        });
+        map.clear();
+        None
+    }
+
+    let mut map = HashMap::from([(0, "0".into()), (1, "one".into())]);
+    assert_eq!(parse(&mut map, 0), Ok("0"));
+    assert!(parse(&mut map, 1).is_err());
+    assert_eq!(parse(&mut map, 2), Ok("2"));
+    map.insert(3, "".into());
+    assert_eq!(first_char(&mut map, 1), Some("o"));
+    assert_eq!(first_char(&mut map, 3), None);
+}
+
+#[test]
+fn polonius_dyn ()
+{
+    fn get_or_insert (mut map: &'_ mut HashMap<u8, String>, key: u8)
+      -> &'_ String
+    {
+        polonius!(dyn |map| -> &'polonius String {
+            if let Some(v) = map.get(&key) {
+                polonius_return!(v);
+            }
+        });
+        map.entry(key).or_insert_with(|| "new".into())
+    }
+
+    assert_eq!(get_or_insert(&mut map(), 0), "zero");
+    assert_eq!(get_or_insert(&mut map(), 9), "new");
+}
+
+#[test]
+fn polonius_labeled ()
+{
+    fn len_then_clear (map: &'_ mut HashMap<u8, String>, key: u8)
+      -> usize
+    {
+        let value: &String = 'found: {
+            let mut map = &mut *map;
+            polonius!('found, |map| -> &'polonius String {
+                if let Some(v) = map.get(&key) {
+                    polonius_return!(v);
+                }
+            });
+            map.entry(key).or_insert_with(|| "new".into())
+        };
+        let len = value.len();
+        map.clear();
+        len
+    }
+
+    assert_eq!(len_then_clear(&mut map(), 0), 4);
+    assert_eq!(len_then_clear(&mut map(), 9), 3);
+}
+
+#[test]
+fn polonius_first_of ()
+{
+    fn lookup (map: &'_ mut HashMap<u8, String>, key: u8)
+      -> &'_ mut String
+    {
+        polonius_first_of!(map => [
+            |m| m.get_mut(&key),
+            |m| m.get_mut(&0),
+        ] else |m| {
+            m.entry(key).or_default()
+        })
+    }
+
+    fn name (map: &'_ mut HashMap<u8, String>, key: u8)
+      -> &'_ str
+    {
+        polonius_first_of!(map => [
+            |m| m.get(&key).map(String::as_str),
+        ] else |m| -> &'polonius str {
+            m.entry(key).or_insert_with(|| "anonymous".into())
+        })
+    }
+
+    let mut map = map();
+    lookup(&mut map, 1).push('!');
+    lookup(&mut map, 5).push('?');
+    assert_eq!(map[&1], "one!");
+    assert_eq!(map[&0], "zero?");
+    assert_eq!(name(&mut map, 9), "anonymous");
+}
+
+#[test]
+fn polonius_loop ()
+{
+    fn first_non_empty (mut map: &'_ mut HashMap<u8, String>)
+      -> Result<&'_ mut String, u8>
+    {
+        let mut key = 0;
+        let missing = polonius_loop!(|map| -> Result<&'polonius mut String, u8> {
+            match map.get_mut(&key) {
+                | Some(v) if v.is_empty() => {
+                    key += 1;
+                    polonius_continue!();
+                },
+                | Some(v) => polonius_return!(Ok(v)),
+                | None => polonius_break!(key),
+            }
+        });
+        map.clear();
+        Err(missing)
+    }
+
+    fn first_empty (mut map: &'_ mut HashMap<u8, String>)
+      -> &'_ mut String
+    {
+        let mut key = 0;
+        let found = polonius_loop!(dyn |map| -> &'polonius mut String, break: &'polonius mut String {
+            if !map.contains_key(&key) {
+                polonius_return!(map.entry(key).or_default());
+            }
+            match map.get_mut(&key) {
+                | Some(v) if v.is_empty() => polonius_break_dependent!(v),
+                | _ => key += 1,
+            }
+        });
+        found.push('!');
+        found
+    }
+
+    let mut map = HashMap::from([(0, "".into()), (1, "".into()), (2, "two".into())]);
+    assert_eq!(first_non_empty(&mut map), Ok(&mut "two".into()));
+    assert_eq!(first_empty(&mut map), "!");
+    assert_eq!(first_empty(&mut map), "!");
+    assert_eq!(map.len(), 3);
+    assert_eq!(first_empty(&mut map), "");
+    assert_eq!(map.len(), 4);
+    let mut map = HashMap::from([(0, "".into())]);
+    assert_eq!(first_non_empty(&mut map), Err(1));
+}
+
+#[test]
+fn polonius_alt ()
+{
+    fn keyword<'c> (mut cursor: &'c mut &'static str)
+      -> Result<&'c mut &'static str, usize>
+    {
+        Ok(polonius_alt!(|cursor| -> &'polonius mut &'static str {
+            cursor.strip_prefix("fn").map(|rest| { *cursor = rest; cursor }),
+            cursor.strip_prefix("let").map(|rest| { *cursor = rest; cursor }),
+        } else {
+            return Err(cursor.len());
+        }))
+    }
+
+    let mut input = "fn main";
+    assert_eq!(*keyword(&mut input).unwrap(), " main");
+    assert_eq!(keyword(&mut input), Err(5));
+}
+
+#[test]
+fn assertions ()
+{
+    let mut map = map();
+    let v = assert_borrowing!(
+        ::polonius_the_crab::polonius::<_, _, ForLt!(<'r> = &'r String)>(&mut map, |map| {
+            match map.get(&0) {
+                | Some(v) => PoloniusResult::Borrowing(v),
+                | None => PoloniusResult::Owned(()),
+            }
+        }),
+        v if v.len() == 4,
+    );
+    assert_eq!(v, "zero");
+    let map = assert_owned!(
+        ::polonius_the_crab::polonius::<_, _, ForLt!(<'r> = &'r String)>(&mut map, |map| {
+            match map.get(&7) {
+                | Some(v) => PoloniusResult::Borrowing(v),
+                | None => PoloniusResult::Owned(map.len()),
+            }
+        }),
+        2,
+    );
+    map.clear();
+}
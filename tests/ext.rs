@@ -0,0 +1,91 @@
+#![forbid(unsafe_code)]
+
+use {
+    ::polonius_the_crab::prelude::*,
+    ::std::collections::HashMap,
+};
+
+type Map = HashMap<u8, String>;
+
+fn map ()
+  -> Map
+{
+    Map::from([(0, "zero".into()), (1, "one".into())])
+}
+
+#[test]
+fn or_insert_with ()
+{
+    fn get_or_insert (map: &'_ mut Map, key: u8)
+      -> &'_ mut String
+    {
+        map.polonius_get(|m| m.get_mut(&key))
+            .or_insert_with(|m| m.entry(key).or_insert_with(|| "new".into()))
+    }
+
+    let mut map = map();
+    get_or_insert(&mut map, 1).push('!');
+    get_or_insert(&mut map, 2).push('?');
+    assert_eq!(map, Map::from([(0, "zero".into()), (1, "one!".into()), (2, "new?".into())]));
+}
+
+#[test]
+fn or_else_chain ()
+{
+    fn lookup (map: &'_ mut Map, key: u8)
+      -> Result<&'_ mut String, usize>
+    {
+        map.polonius_get(|m| m.get_mut(&key))
+            .or_else(|m| m.get_mut(&(key + 1)))
+            .or_else(|m| m.values_mut().find(|v| v.is_empty()))
+            .into_result()
+            .map_err(|m| m.len())
+    }
+
+    let mut map = map();
+    assert_eq!(lookup(&mut map, 0).unwrap(), "zero");
+    assert_eq!(lookup(&mut map, 42), Err(2));
+    lookup(&mut map, 0).unwrap().clear();
+    assert_eq!(lookup(&mut map, 42), Ok(&mut String::new()));
+    lookup(&mut map, 42).unwrap().push('!');
+    // The first hit is kept: the other lookups are not even run.
+    assert_eq!(
+        map.polonius_get(|m| m.get_mut(&1))
+            .or_else(|_| unreachable!())
+            .into_result()
+            .ok()
+            .map(|v| &v[..]),
+        Some("one"),
+    );
+}
+
+#[test]
+fn or_try_else ()
+{
+    fn parsed<'m> (map: &'m mut Map, key: u8, value: &str)
+      -> Result<&'m mut String, ::core::num::ParseIntError>
+    {
+        map.polonius_get(|m| m.get_mut(&key))
+            .or_try_else(|m| {
+                let _: u32 = value.parse()?;
+                Ok(m.entry(key).or_insert_with(|| value.into()))
+            })
+    }
+
+    fn non_empty (map: &'_ mut Map, key: u8)
+      -> Option<&'_ mut String>
+    {
+        map.polonius_get(|m| m.get_mut(&key).filter(|v| !v.is_empty()))
+            .or_try_else(|m| m.values_mut().find(|v| !v.is_empty()))
+    }
+
+    let mut map = map();
+    assert_eq!(parsed(&mut map, 0, "nan").unwrap(), "zero");
+    assert!(parsed(&mut map, 2, "nan").is_err());
+    assert_eq!(parsed(&mut map, 2, "2").unwrap(), "2");
+    map.get_mut(&1).unwrap().clear();
+    map.remove(&2);
+    assert_eq!(non_empty(&mut map, 1).unwrap(), "zero");
+    map.clear();
+    assert_eq!(non_empty(&mut map, 1), None);
+}
@@ -0,0 +1,87 @@
+#![forbid(unsafe_code)]
+
+use {
+    ::polonius_the_crab::prelude::*,
+    ::std::collections::{BTreeMap, HashMap},
+};
+
+struct Config {
+    user: HashMap<&'static str, String>,
+    defaults: BTreeMap<&'static str, String>,
+}
+
+impl Config {
+    /// user setting, then its alias, then the default, else a new (empty) user
+    /// setting.
+    fn setting (&mut self, key: &'static str, alias: &'static str)
+      -> &mut String
+    {
+        polonius_first_of!(self => [
+            |this| this.user.get_mut(key),
+            |this| this.user.get_mut(alias),
+            |this| this.defaults.get_mut(key),
+        ] else |this| {
+            this.user.entry(key).or_default()
+        })
+    }
+}
+
+fn config ()
+  -> Config
+{
+    Config {
+        user: HashMap::from([("color", "red".into()), ("colour", "blue".into())]),
+        defaults: BTreeMap::from([("font", "mono".into())]),
+    }
+}
+
+#[test]
+fn first_hit_wins ()
+{
+    let mut config = config();
+    config.setting("color", "colour").push('!');
+    assert_eq!(config.user["color"], "red!");
+    assert_eq!(config.user["colour"], "blue");
+}
+
+#[test]
+fn falls_back ()
+{
+    let mut config = config();
+    config.setting("tint", "colour").push('?');
+    assert_eq!(config.user["colour"], "blue?");
+    config.setting("font", "typeface").push('!');
+    assert_eq!(config.defaults["font"], "mono!");
+}
+
+#[test]
+fn else_gets_the_input_back ()
+{
+    let mut config = config();
+    config.setting("size", "length").push_str("12");
+    assert_eq!(config.user["size"], "12");
+    assert_eq!(config.user.len(), 3);
+}
+
+#[test]
+fn typed_and_early_return ()
+{
+    fn first_even_or_odd (v: &'_ mut Vec<i32>)
+      -> Option<&'_ i32>
+    {
+        Some(polonius_first_of!(v => [
+            |v| v.iter().find(|&&x| x % 2 == 0),
+        ] else |v| -> &'polonius i32 {
+            if v.is_empty() {
+                return None;
+            }
+            v.push(0);
+            v.last().unwrap()
+        }))
+    }
+    assert_eq!(first_even_or_odd(&mut vec![1, 2]), Some(&2));
+    let mut v = vec![1];
+    assert_eq!(first_even_or_odd(&mut v), Some(&0));
+    assert_eq!(v, [1, 0]);
+    assert_eq!(first_even_or_odd(&mut vec![]), None);
+}
@@ -0,0 +1,86 @@
+#![forbid(unsafe_code)]
+
+use ::polonius_the_crab::init::{get_or_init, get_or_try_init, OptionExt};
+
+#[derive(Debug, PartialEq)]
+enum State {
+    Disconnected { attempts: u32 },
+    Ready(String),
+}
+
+fn ready (state: &'_ mut State)
+  -> Option<&'_ mut String>
+{
+    match state {
+        | State::Ready(conn) => Some(conn),
+        | State::Disconnected { .. } => None,
+    }
+}
+
+/// Fails on the first attempt.
+fn connect (state: &'_ mut State)
+  -> Result<State, String>
+{
+    match state {
+        | State::Disconnected { attempts } if *attempts == 0 => {
+            *attempts += 1;
+            Err("connection refused".into())
+        },
+        | State::Disconnected { attempts } => {
+            Ok(State::Ready(format!("conn#{attempts}")))
+        },
+        | State::Ready(_) => unreachable!(),
+    }
+}
+
+#[test]
+fn get_or_try_init_result ()
+{
+    let mut state = State::Disconnected { attempts: 0 };
+    assert_eq!(get_or_try_init(&mut state, ready, connect), Err("connection refused".into()));
+    // `init` was able to update the state, but the slot was not overwritten.
+    assert_eq!(state, State::Disconnected { attempts: 1 });
+
+    get_or_try_init(&mut state, ready, connect).unwrap().push('!');
+    assert_eq!(state, State::Ready("conn#1!".into()));
+    // Already initialized: `init` is not called.
+    let conn = get_or_try_init(&mut state, ready, |_| -> Result<_, String> { unreachable!() });
+    assert_eq!(conn.unwrap(), "conn#1!");
+}
+
+#[test]
+fn get_or_try_init_option ()
+{
+    let mut slot = State::Disconnected { attempts: 0 };
+    assert_eq!(get_or_try_init(&mut slot, ready, |_| None), None);
+    let conn = get_or_try_init(&mut slot, ready, |_| Some(State::Ready("conn".into())));
+    assert_eq!(conn, Some(&mut "conn".into()));
+}
+
+#[test]
+fn get_or_init_infallible ()
+{
+    let mut state = State::Disconnected { attempts: 0 };
+    get_or_init(&mut state, ready, |_| State::Ready(String::new())).push('a');
+    get_or_init(&mut state, ready, |_| unreachable!()).push('b');
+    assert_eq!(state, State::Ready("ab".into()));
+}
+
+#[test]
+#[should_panic(expected = "freshly `init`ialized")]
+fn projection_must_succeed_after_init ()
+{
+    let mut state = State::Disconnected { attempts: 0 };
+    get_or_init(&mut state, ready, |_| State::Disconnected { attempts: 1 });
+}
+
+#[test]
+fn option_ext ()
+{
+    let mut cache = None::<Vec<u8>>;
+    assert_eq!(OptionExt::get_or_try_insert_with(&mut cache, || Err::<_, ()>(())), Err(()));
+    assert_eq!(cache, None);
+    OptionExt::get_or_try_insert_with(&mut cache, || Ok::<_, ()>(vec![1])).unwrap().push(2);
+    OptionExt::get_or_try_insert_with(&mut cache, || None).unwrap().push(3);
+    assert_eq!(cache, Some(vec![1, 2, 3]));
+}
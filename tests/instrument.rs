@@ -0,0 +1,101 @@
+#![forbid(unsafe_code)]
+
+use {
+    ::polonius_the_crab::{
+        instrument::{self, Counters},
+        prelude::*,
+    },
+    ::std::{
+        collections::HashMap,
+        sync::Mutex,
+    },
+};
+
+/// The registry is global: `reset()`/`take()` must not run concurrently with
+/// the other tests.
+static REGISTRY: Mutex<()> = Mutex::new(());
+
+/// The counters of the call site at `line` of this file.
+fn counters_at (line: u32)
+  -> Counters
+{
+    instrument::snapshot()
+        .find(|c| c.location.file() == file!() && c.location.line() == line)
+        .unwrap_or_else(|| panic!("no call site recorded at line {line}"))
+}
+
+fn get_or_insert<V : Default> (mut map: &'_ mut HashMap<u8, V>, key: u8)
+  -> (&'_ V, u32)
+{
+    let line = line!() + 1;
+    polonius!(|map| -> (&'polonius V, u32) {
+        if let Some(v) = map.get(&key) {
+            polonius_return!((v, line));
+        }
+    });
+    (map.entry(key).or_default(), line)
+}
+
+#[test]
+fn hits_and_misses ()
+{
+    let _guard = REGISTRY.lock().unwrap();
+    let mut map = HashMap::<_, i32>::new();
+    let before = counters_at(get_or_insert(&mut map, 0).1);
+    for key in [1, 2, 1, 1, 3] {
+        get_or_insert(&mut map, key);
+    }
+    // Other instantiations of the generic function share the call site.
+    get_or_insert(&mut HashMap::<_, String>::new(), 0);
+    let after = counters_at(get_or_insert(&mut map, 0).1);
+    assert_eq!(after.hits - before.hits, 2 + 1);
+    assert_eq!(after.misses - before.misses, 3 + 1);
+}
+
+/// First non-zero element.
+fn first_non_zero (mut vec: &'_ mut Vec<i32>)
+  -> (Option<&'_ mut i32>, u32)
+{
+    let mut i = 0;
+    let line = line!() + 1;
+    polonius_loop!(|vec| -> (Option<&'polonius mut i32>, u32) {
+        match vec.get_mut(i) {
+            | Some(&mut 0) => i += 1,
+            | Some(found) => polonius_return!((Some(found), line)),
+            | None => polonius_break!(),
+        }
+    });
+    (None, line)
+}
+
+#[test]
+fn loop_iterations ()
+{
+    let _guard = REGISTRY.lock().unwrap();
+    let line = first_non_zero(&mut vec![]).1;
+    let before = counters_at(line);
+    *first_non_zero(&mut vec![0, 0, 0, 42]).0.unwrap() += 1;
+    let after = counters_at(line);
+    // One `Borrowing` return, after three `Owned` `continue`s.
+    assert_eq!(after.hits - before.hits, 1);
+    assert_eq!(after.misses - before.misses, 3);
+}
+
+#[test]
+fn take_and_reset ()
+{
+    let _guard = REGISTRY.lock().unwrap();
+    let mut map = HashMap::<_, ()>::new();
+    let line = get_or_insert(&mut map, 0).1;
+    get_or_insert(&mut map, 0);
+    let taken: Vec<_> = instrument::take().collect();
+    assert!(taken.iter().any(|c| c.location.line() == line && c.hits > 0));
+    assert!(instrument::snapshot().all(|c| (c.hits, c.misses) == (0, 0)));
+
+    get_or_insert(&mut map, 0);
+    assert_eq!(counters_at(line).hits, 1);
+    instrument::reset();
+    assert_eq!(counters_at(line), Counters { hits: 0, misses: 0, ..counters_at(line) });
+    // A handful of call sites is far from the `CAPACITY`.
+    assert_eq!(instrument::overflowed(), 0);
+}
@@ -0,0 +1,97 @@
+#![forbid(unsafe_code)]
+
+use {
+    ::polonius_the_crab::{
+        collections::GetOrInsertBorrowed,
+        intern::Interner,
+    },
+    ::std::{
+        cell::Cell,
+        collections::BTreeMap,
+    },
+};
+
+thread_local! {
+    static CLONES: Cell<usize> = const { Cell::new(0) };
+}
+
+/// A key type counting how many times it gets [`ToOwned::to_owned()`]-ed.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Key(u32);
+
+impl Clone for Key {
+    fn clone (&self)
+      -> Self
+    {
+        CLONES.with(|it| it.set(it.get() + 1));
+        Key(self.0)
+    }
+}
+
+fn clones ()
+  -> usize
+{
+    CLONES.with(Cell::get)
+}
+
+#[test]
+fn interner_dedups ()
+{
+    let mut interner = Interner::new();
+    assert!(interner.is_empty());
+    let a = interner.intern("a") as *const str;
+    let b = interner.intern("b") as *const str;
+    assert!(!::core::ptr::eq(a, b));
+    assert!(::core::ptr::eq(a, interner.intern("a")));
+    assert!(::core::ptr::eq(b, interner.get("b").unwrap()));
+    assert_eq!(interner.get("c"), None);
+    assert_eq!(interner.iter().collect::<Vec<_>>(), ["a", "b"]);
+}
+
+#[test]
+fn interner_only_allocates_on_miss ()
+{
+    let mut interner = Interner::<Key>::default();
+    let start = clones();
+    for i in [1, 2, 1, 1, 3, 2] {
+        assert_eq!(*interner.intern(&Key(i)), Key(i));
+    }
+    assert_eq!(clones() - start, 3);
+    assert_eq!(interner.len(), 3);
+}
+
+#[test]
+fn interner_of_slices ()
+{
+    let mut interner = Interner::<[u8]>::new();
+    let interned: &[u8] = interner.intern(b"bytes");
+    assert_eq!(interned, b"bytes");
+    assert_eq!(interner.len(), 1);
+}
+
+#[test]
+fn btree_map_get_or_insert_borrowed ()
+{
+    let mut map = BTreeMap::<Key, Vec<u32>>::new();
+    let start = clones();
+    for (k, v) in [(1, 10), (2, 20), (1, 11), (1, 12)] {
+        map.get_or_insert_borrowed(&Key(k), |_| vec![]).push(v);
+    }
+    assert_eq!(clones() - start, 2);
+    assert_eq!(map[&Key(1)], [10, 11, 12]);
+    assert_eq!(map[&Key(2)], [20]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn hash_map_get_or_insert_borrowed ()
+{
+    let mut map = ::std::collections::HashMap::<String, usize>::new();
+    for word in "to be or not to be".split(' ') {
+        *map.get_or_insert_borrowed(word, str::len) += 1;
+    }
+    assert_eq!(map["to"], 4);
+    assert_eq!(map["be"], 4);
+    assert_eq!(map["or"], 3);
+    assert_eq!(map["not"], 4);
+}
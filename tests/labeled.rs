@@ -0,0 +1,121 @@
+#![forbid(unsafe_code)]
+
+use {
+    ::polonius_the_crab::prelude::*,
+    ::std::collections::HashMap,
+};
+
+#[derive(Debug, Default, PartialEq)]
+struct Account {
+    balance: i64,
+    history: Vec<i64>,
+}
+
+/// Applies the `amount` to the `name`d account (opening it, or falling back
+/// to the `"suspense"` account for unknown `ids`), and then logs it, all
+/// within a single function body.
+fn apply (
+    mut accounts: &'_ mut HashMap<String, Account>,
+    name: &'_ str,
+    amount: i64,
+    log: &'_ mut Vec<String>,
+) -> i64
+{
+    let account = 'found: {
+        let opened = polonius!('found, |accounts| -> &'polonius mut Account {
+            if let Some(account) = accounts.get_mut(name) {
+                polonius_return!(account);
+            }
+            if name.starts_with('#') {
+                exit_polonius!(false);
+            }
+            true
+        });
+        if opened {
+            log.push(format!("opened {name}"));
+            accounts.entry(name.to_owned()).or_default()
+        } else {
+            accounts.entry("suspense".to_owned()).or_default()
+        }
+    };
+    account.balance += amount;
+    account.history.push(amount);
+    let balance = account.balance;
+    log.push(format!("{name}: {amount:+} => {balance}"));
+    balance
+}
+
+#[test]
+fn composed ()
+{
+    let mut accounts = HashMap::new();
+    let mut log = vec![];
+    assert_eq!(apply(&mut accounts, "alice", 10, &mut log), 10);
+    assert_eq!(apply(&mut accounts, "alice", -3, &mut log), 7);
+    assert_eq!(apply(&mut accounts, "#42", 5, &mut log), 5);
+    assert_eq!(accounts["alice"].history, [10, -3]);
+    assert_eq!(accounts["suspense"].balance, 5);
+    assert_eq!(log, [
+        "opened alice",
+        "alice: +10 => 10",
+        "alice: -3 => 7",
+        "#42: +5 => 5",
+    ]);
+}
+
+#[test]
+fn try_and_dyn ()
+{
+    fn parsed_len (
+        cache: &'_ mut HashMap<u8, String>,
+        key: u8,
+    ) -> Result<usize, ::core::num::ParseIntError>
+    {
+        let s: &String = 'cached: {
+            // local reborrow, so that `cache` is usable again after `s`.
+            let mut cache = &mut *cache;
+            polonius!('cached, dyn |cache| -> Result<&'polonius String, _> {
+                if let Some(s) = cache.get(&key) {
+                    let _: u8 = polonius_try!(s.parse());
+                    polonius_return!(Ok(s));
+                }
+            });
+            Ok(cache.entry(key).or_insert_with(|| key.to_string()))
+        }?;
+        let len = s.len();
+        cache.insert(key.wrapping_add(1), "nan".into());
+        Ok(len)
+    }
+    let mut cache = HashMap::from([(0, "10".into())]);
+    assert_eq!(parsed_len(&mut cache, 0), Ok(2));
+    assert_eq!(parsed_len(&mut cache, 100), Ok(3));
+    assert!(parsed_len(&mut cache, 1).is_err());
+}
+
+#[test]
+fn nested_in_a_loop ()
+{
+    /// Sum, over the `keys`, of the lengths of their values, inserting the
+    /// missing ones.
+    fn total_len (map: &'_ mut HashMap<u8, String>, keys: &'_ [u8])
+      -> usize
+    {
+        let mut total = 0;
+        for &key in keys {
+            let value: &String = 'value: {
+                let mut map = &mut *map;
+                polonius!('value, |map| -> &'polonius String {
+                    if let Some(value) = map.get(&key) {
+                        polonius_return!(value);
+                    }
+                });
+                map.entry(key).or_insert_with(|| "?".repeat(key.into()))
+            };
+            total += value.len();
+        }
+        total
+    }
+    let mut map = HashMap::from([(0, "zero".into())]);
+    assert_eq!(total_len(&mut map, &[0, 2, 0, 3]), 4 + 2 + 4 + 3);
+    assert_eq!(map.len(), 3);
+}
@@ -0,0 +1,94 @@
+#![forbid(unsafe_code)]
+
+//! `polonius_loop()`, without any macros.
+
+use {
+    ::core::convert::Infallible,
+    ::polonius_the_crab::{
+        ForLt,
+        polonius_loop,
+        LoopExit,
+        LoopStep,
+    },
+    ::std::collections::HashMap,
+};
+
+type MutI32 = ForLt!(<'r> = &'r mut i32);
+type Never = ForLt!(<'r> = Infallible);
+
+/// Looks the keys `start, start + 1, …` up, returning the first value found,
+/// or inserting under `limit` if none.
+fn first_from (map: &'_ mut HashMap<u8, i32>, start: u8, limit: u8)
+  -> &'_ mut i32
+{
+    let mut key = start;
+    let exit = polonius_loop::<_, _, MutI32, Never>(map, |map| {
+        if let Some(value) = map.get_mut(&key) {
+            return LoopStep::Return(value);
+        }
+        if key == limit {
+            return LoopStep::BreakOwned(key);
+        }
+        key += 1;
+        LoopStep::Continue
+    });
+    match exit {
+        | LoopExit::Return(value) => value,
+        | LoopExit::BreakDependent(unreachable) => match unreachable {},
+        | LoopExit::BreakOwned { value: key, input_borrow: map } => {
+            map.entry(key).or_insert(0)
+        },
+    }
+}
+
+#[test]
+fn return_or_break_owned ()
+{
+    let mut map = HashMap::from([(3, 30)]);
+    *first_from(&mut map, 1, 5) += 1;
+    assert_eq!(map[&3], 31);
+    *first_from(&mut map, 4, 5) += 1;
+    assert_eq!(map[&5], 1);
+}
+
+#[test]
+fn break_dependent ()
+{
+    let mut v = vec![1, 3, 5, 6, 7];
+    let mut i = 0;
+    let mut steps = 0;
+    let exit = polonius_loop::<_, (), Never, MutI32>(&mut v, |v| {
+        steps += 1;
+        match v.get_mut(i) {
+            | Some(x) if *x % 2 == 0 => LoopStep::BreakDependent(x),
+            | Some(_) => {
+                i += 1;
+                LoopStep::Continue
+            },
+            | None => LoopStep::BreakOwned(()),
+        }
+    });
+    match exit {
+        | LoopExit::BreakDependent(x) => *x *= 10,
+        | _ => unreachable!(),
+    }
+    assert_eq!(steps, 4);
+    assert_eq!(v, [1, 3, 5, 60, 7]);
+}
+
+#[test]
+fn input_borrow_is_given_back ()
+{
+    let mut v = vec![1, 3];
+    let exit = polonius_loop::<_, usize, Never, MutI32>(&mut v, |v| {
+        match v.iter_mut().position(|x| *x % 2 == 0) {
+            | Some(i) => LoopStep::BreakDependent(&mut v[i]),
+            | None => LoopStep::BreakOwned(v.len()),
+        }
+    });
+    match exit {
+        | LoopExit::BreakOwned { value: 2, input_borrow: v } => v.push(2),
+        | _ => unreachable!(),
+    }
+    assert_eq!(v, [1, 3, 2]);
+}
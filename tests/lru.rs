@@ -0,0 +1,106 @@
+#![forbid(unsafe_code)]
+
+use ::polonius_the_crab::lru::LruCache;
+
+fn keys<V> (cache: &'_ LruCache<u32, V>)
+  -> Vec<u32>
+{
+    cache.iter().map(|(&k, _)| k).collect()
+}
+
+#[test]
+fn eviction_order ()
+{
+    let mut cache = LruCache::new(3);
+    for k in 1..= 3 {
+        assert_eq!(cache.insert(k, k * 10), None);
+    }
+    assert_eq!(keys(&cache), [3, 2, 1]);
+
+    // Using `1` promotes it; `2` is now the least recently used…
+    *cache.get_mut(&1).unwrap() += 1;
+    assert_eq!(keys(&cache), [1, 3, 2]);
+    // … and thus the one to be evicted.
+    assert_eq!(cache.insert(4, 40), Some((2, 20)));
+    assert_eq!(keys(&cache), [4, 1, 3]);
+
+    // `peek` does not promote.
+    assert_eq!(cache.peek(&3), Some(&30));
+    assert_eq!(cache.insert(5, 50), Some((3, 30)));
+    assert_eq!(keys(&cache), [5, 4, 1]);
+
+    // Re-inserting an existing key replaces it, and promotes it.
+    assert_eq!(cache.insert(1, 0), Some((1, 11)));
+    assert_eq!(keys(&cache), [1, 5, 4]);
+
+    assert_eq!(cache.pop_lru(), Some((4, 40)));
+    assert_eq!(cache.pop_lru(), Some((5, 50)));
+    assert_eq!(cache.pop_lru(), Some((1, 0)));
+    assert_eq!(cache.pop_lru(), None);
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn get_or_insert_with ()
+{
+    let mut cache = LruCache::new(2);
+    cache.get_or_insert_with(1, Vec::new).push('a');
+    cache.get_or_insert_with(2, Vec::new).push('b');
+    // hit: promotes `1`.
+    cache.get_or_insert_with(1, || unreachable!()).push('c');
+    assert_eq!(keys(&cache), [1, 2]);
+    // miss: evicts `2`.
+    cache.get_or_insert_with(3, Vec::new).push('d');
+    assert_eq!(keys(&cache), [3, 1]);
+    assert_eq!(cache.peek(&1).unwrap(), &['a', 'c']);
+    assert!(!cache.contains_key(&2));
+    assert_eq!(cache.len(), cache.capacity());
+}
+
+#[test]
+fn get_or_try_insert_with ()
+{
+    let mut cache = LruCache::<u32, String>::new(2);
+    cache.insert(1, "one".into());
+    cache.insert(2, "two".into());
+
+    let res: Result<&mut String, ()> = cache.get_or_try_insert_with(3, || Err(()));
+    assert!(res.is_err());
+    // Nothing got evicted nor promoted.
+    assert_eq!(keys(&cache), [2, 1]);
+
+    assert_eq!(cache.get_or_try_insert_with(3, || None), None);
+    let three = cache.get_or_try_insert_with(3, || Some("three".into())).unwrap();
+    three.push('!');
+    assert_eq!(keys(&cache), [3, 2]);
+    assert_eq!(cache.peek(&3).unwrap(), "three!");
+
+    fn lookup (cache: &'_ mut LruCache<u32, String>, n: u32)
+      -> Result<&'_ mut String, ::core::num::ParseIntError>
+    {
+        let value = cache.get_or_try_insert_with(n, || {
+            Ok::<_, ::core::num::ParseIntError>(n.to_string())
+        })?;
+        Ok(value)
+    }
+    assert_eq!(lookup(&mut cache, 2).unwrap(), "two");
+    assert_eq!(keys(&cache), [2, 3]);
+}
+
+#[test]
+fn swap_remove_bookkeeping ()
+{
+    // Exercise the relocation of entries within the backing storage.
+    let mut cache = LruCache::new(4);
+    for round in 0..50_u32 {
+        for k in [round % 7, round % 5, round % 3] {
+            *cache.get_or_insert_with(k, || 0) += 1;
+        }
+        let keys = keys(&cache);
+        assert!(keys.len() <= 4);
+        for k in &keys {
+            assert!(cache.contains_key(k));
+        }
+        assert_eq!(keys.len(), cache.len());
+    }
+}
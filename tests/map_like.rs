@@ -0,0 +1,118 @@
+#![forbid(unsafe_code)]
+
+use ::polonius_the_crab::map_like::{self, MapLike};
+
+/// An arena-like container: keys are the indices of the slots.
+#[derive(Default)]
+struct Slots<V> {
+    slots: Vec<Option<V>>,
+    lookups: usize,
+}
+
+impl<V> MapLike for Slots<V> {
+    type Key = usize;
+    type Value = V;
+
+    fn get_mut (&mut self, &key: &'_ usize)
+      -> Option<&'_ mut V>
+    {
+        self.lookups += 1;
+        self.slots.get_mut(key)?.as_mut()
+    }
+
+    fn insert (&mut self, key: usize, value: V)
+    {
+        if key >= self.slots.len() {
+            self.slots.resize_with(key + 1, || None);
+        }
+        self.slots[key] = Some(value);
+    }
+}
+
+/// Same, but with `insert_and_get_mut()` overridden.
+#[derive(Default)]
+struct FastSlots<V>(Slots<V>);
+
+impl<V> MapLike for FastSlots<V> {
+    type Key = usize;
+    type Value = V;
+
+    fn get_mut (&mut self, key: &'_ usize)
+      -> Option<&'_ mut V>
+    {
+        self.0.get_mut(key)
+    }
+
+    fn insert (&mut self, key: usize, value: V)
+    {
+        self.0.insert(key, value)
+    }
+
+    fn insert_and_get_mut (&mut self, key: usize, value: V)
+      -> &'_ mut V
+    {
+        self.insert(key, value);
+        self.0.slots[key].as_mut().unwrap()
+    }
+}
+
+#[test]
+fn provided_insert_and_get_mut ()
+{
+    let mut slots = Slots::<String>::default();
+    map_like::get_or_insert_with(&mut slots, 2, || "two".into()).push('!');
+    // miss: lookup, then the re-lookup of the provided `insert_and_get_mut`.
+    assert_eq!(slots.lookups, 2);
+    map_like::get_or_insert_with(&mut slots, 2, || unreachable!()).push('!');
+    assert_eq!(slots.lookups, 3);
+    assert_eq!(slots.slots, [None, None, Some("two!!".into())]);
+}
+
+#[test]
+fn overridden_insert_and_get_mut ()
+{
+    let mut slots = FastSlots::<u8>::default();
+    *map_like::get_or_insert_with(&mut slots, 0, || 41) += 1;
+    assert_eq!(slots.0.lookups, 1);
+    assert_eq!(slots.0.slots, [Some(42)]);
+}
+
+#[test]
+fn try_insert ()
+{
+    let mut slots = Slots::<i32>::default();
+    let res = map_like::get_or_try_insert_with(&mut slots, 1, || "nope".parse());
+    assert!(res.is_err());
+    assert!(slots.slots.is_empty());
+    assert_eq!(map_like::get_or_try_insert_with(&mut slots, 1, || None), None);
+    let one = map_like::get_or_try_insert_with(&mut slots, 1, || "1".parse::<i32>());
+    assert_eq!(one, Ok(&mut 1));
+    assert_eq!(map_like::get_or_try_insert_with(&mut slots, 1, || None), Some(&mut 1));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn std_maps ()
+{
+    fn count<M : MapLike<Key = &'static str, Value = usize>> (map: &'_ mut M)
+    {
+        for word in "a b a c a".split(' ') {
+            *map_like::get_or_insert_with(map, word, || 0) += 1;
+        }
+    }
+
+    let mut btree_map = ::std::collections::BTreeMap::new();
+    count(&mut btree_map);
+    assert_eq!(btree_map["a"], 3);
+
+    #[cfg(feature = "std")] {
+        let mut hash_map = ::std::collections::HashMap::new();
+        count(&mut hash_map);
+        assert_eq!(hash_map["a"], 3);
+    }
+
+    let mut lru = ::polonius_the_crab::lru::LruCache::new(2);
+    count(&mut lru);
+    // `b` got evicted by `c`.
+    assert_eq!(lru.iter().collect::<Vec<_>>(), [(&"a", &3), (&"c", &1)]);
+}
@@ -0,0 +1,90 @@
+#![forbid(unsafe_code)]
+
+use {
+    ::polonius_the_crab::memo::Memo,
+    ::std::{
+        cell::Cell,
+        collections::BTreeMap,
+    },
+};
+
+#[derive(Debug, PartialEq)]
+struct Error(&'static str);
+
+#[test]
+fn computes_once ()
+{
+    let calls = Cell::new(0);
+    let mut memo = Memo::<u8, String>::new();
+    for _ in 0 .. 3 {
+        let value = memo.get_or_compute(42, |n| {
+            calls.set(calls.get() + 1);
+            n.to_string()
+        });
+        assert_eq!(value, "42");
+    }
+    assert_eq!(calls.get(), 1);
+    assert_eq!(memo.map().len(), 1);
+}
+
+#[test]
+fn try_compute_result ()
+{
+    let mut memo = Memo::<i32, u32, BTreeMap<_, _>>::new();
+    let compute = |&n: &i32| u32::try_from(n).map_err(|_| Error("negative"));
+    assert_eq!(memo.get_or_try_compute(-1, compute), Err(Error("negative")));
+    assert_eq!(memo.get(&-1), None);
+    assert_eq!(memo.get_or_try_compute(1, compute), Ok(&1));
+    // cached: the error path is no longer reachable
+    assert_eq!(memo.get_or_try_compute(1, |_| Err(Error("unreachable"))), Ok(&1));
+}
+
+#[test]
+fn try_compute_option ()
+{
+    let mut memo = Memo::<&str, i32>::new();
+    assert_eq!(memo.get_or_try_compute("x", |s| s.parse().ok()), None);
+    assert_eq!(memo.get_or_try_compute("7", |s| s.parse().ok()), Some(&7));
+}
+
+fn parse_sum<'memo> (memo: &'memo mut Memo<String, i64>, s: &'_ str)
+  -> Result<&'memo i64, Error>
+{
+    let sum = memo.get_or_try_compute(s.to_owned(), |s| {
+        s.split('+').try_fold(0, |acc, term| {
+            term.trim().parse::<i64>()
+                .map(|term| acc + term)
+                .map_err(|_| Error("not a number"))
+        })
+    })?;
+    Ok(sum)
+}
+
+#[test]
+fn try_compute_question_mark ()
+{
+    let mut memo = Memo::new();
+    assert_eq!(parse_sum(&mut memo, "1 + 2 + 3"), Ok(&6));
+    assert_eq!(parse_sum(&mut memo, "1 + two"), Err(Error("not a number")));
+    assert_eq!(memo.map().len(), 1);
+}
+
+fn collatz_len (memo: &'_ mut Memo<u64, u32>, n: u64)
+  -> &'_ u32
+{
+    memo.get_or_compute_recursive(n, |memo, &n| match n {
+        | 1 => 1,
+        | _ if n % 2 == 0 => 1 + *collatz_len(memo, n / 2),
+        | _ => 1 + *collatz_len(memo, 3 * n + 1),
+    })
+}
+
+#[test]
+fn compute_recursive ()
+{
+    let mut memo = Memo::new();
+    assert_eq!(*collatz_len(&mut memo, 27), 112);
+    // every intermediary step got cached.
+    assert_eq!(memo.map().len(), 112);
+    assert_eq!(memo.get(&9232), Some(&(112 - 77)));
+}
@@ -0,0 +1,170 @@
+#![forbid(unsafe_code)]
+
+//! The macros within a loop of the body target the `polonius!` /
+//! `polonius_loop!` invocation, not that inner loop, be it with or without the
+//! `polonius` Cargo feature.
+
+use {
+    ::polonius_the_crab::prelude::*,
+    ::std::collections::HashMap,
+};
+
+fn count_up_to_two (mut map: &'_ mut HashMap<u8, String>)
+  -> Result<&'_ mut String, i32>
+{
+    let mut count = 0;
+    polonius_loop!(|map| -> Result<&'polonius mut String, i32> {
+        let mut spins = 0;
+        loop {
+            if count >= 2 {
+                polonius_break!();
+            }
+            spins += 1;
+            if spins == 3 {
+                break;
+            }
+        }
+        count += 1;
+        if let Some(v) = map.get_mut(&42) {
+            polonius_return!(Ok(v));
+        }
+    });
+    Err(count)
+}
+
+#[test]
+fn break_within_a_loop ()
+{
+    let mut map = HashMap::new();
+    assert_eq!(count_up_to_two(&mut map), Err(2));
+    map.insert(42, String::new());
+    assert_eq!(count_up_to_two(&mut map), Ok(&mut String::new()));
+}
+
+fn lookup<'map> (mut map: &'map mut HashMap<u8, String>, keys: &'_ [u8])
+  -> (Option<&'map mut String>, u32)
+{
+    let mut rounds = 0;
+    let missing = polonius_loop!(|map| -> (Option<&'polonius mut String>, u32) {
+        rounds += 1;
+        for &key in keys {
+            match map.get(&key).map(String::len) {
+                | None => polonius_break!(key),
+                | Some(0) => {},
+                | Some(len) if len >= 3 => {
+                    polonius_return!((map.get_mut(&key), rounds));
+                },
+                | Some(_) => {
+                    map.get_mut(&key).unwrap().push('+');
+                    polonius_continue!();
+                },
+            }
+        }
+        polonius_return!((None, rounds));
+    });
+    map.insert(missing, String::new());
+    (None, rounds)
+}
+
+#[test]
+fn break_and_continue_within_a_for_loop ()
+{
+    let mut map = HashMap::from([
+        (0, "a".to_owned()),
+        (1, "bcd".to_owned()),
+    ]);
+    // `polonius_continue!` starts the next round of the `polonius_loop!`.
+    let (found, rounds) = lookup(&mut map, &[0, 1]);
+    assert_eq!((found.unwrap().as_str(), rounds), ("a++", 3));
+    // `polonius_break!` ends the `polonius_loop!`.
+    assert_eq!(lookup(&mut map, &[2, 1]), (None, 1));
+    assert_eq!(map[&2], "");
+    let (found, rounds) = lookup(&mut map, &[2, 1]);
+    assert_eq!((found.unwrap().as_str(), rounds), ("bcd", 1));
+    assert_eq!(lookup(&mut map, &[]), (None, 1));
+}
+
+fn find_from (mut map: &'_ mut HashMap<u8, String>, mut key: u8)
+  -> Option<&'_ mut String>
+{
+    let mut iterations = 0;
+    let found = polonius_loop!(|map| -> Option<&'polonius mut String>, break: &'polonius mut String {
+        iterations += 1;
+        while key < 10 {
+            if map.contains_key(&key) {
+                polonius_break_dependent!(map.get_mut(&key).unwrap());
+            }
+            key += 1;
+        }
+        polonius_return!(None);
+    });
+    assert_eq!(iterations, 1);
+    found.push('!');
+    Some(found)
+}
+
+#[test]
+fn break_dependent_within_a_while_loop ()
+{
+    let mut map = HashMap::from([(3, "three".to_owned())]);
+    assert_eq!(find_from(&mut map, 0).unwrap(), "three!");
+    assert_eq!(find_from(&mut map, 4), None);
+}
+
+fn exit_within_a_loop (mut v: &'_ mut Vec<i32>, target: i32)
+  -> (&'_ mut i32, usize)
+{
+    let mut steps = 0;
+    let at = polonius!(|v| -> (&'polonius mut i32, usize) {
+        let mut i = 0;
+        loop {
+            if i == v.len() {
+                exit_polonius!(None);
+            }
+            if v[i] == target {
+                break;
+            }
+            if v[i] > target {
+                exit_polonius!(Some(i));
+            }
+            i += 1;
+            steps += 1;
+        }
+        polonius_return!((&mut v[i], steps));
+    });
+    let at = at.unwrap_or(v.len());
+    v.insert(at, target);
+    (&mut v[at], steps)
+}
+
+#[test]
+fn exit_within_a_loop_ ()
+{
+    let mut v = vec![1, 3, 5];
+    assert_eq!(exit_within_a_loop(&mut v, 3), (&mut 3, 1));
+    assert_eq!(exit_within_a_loop(&mut v, 4), (&mut 4, 2));
+    assert_eq!(exit_within_a_loop(&mut v, 6), (&mut 6, 4));
+    assert_eq!(v, [1, 3, 4, 5, 6]);
+}
+
+#[test]
+fn labeled_return_within_a_loop ()
+{
+    let mut names = HashMap::from([(7, "seven".to_owned())]);
+    let mut tried = 0;
+    let name = 'found: {
+        let mut names = &mut names;
+        polonius!('found, |names| -> &'polonius mut String {
+            for id in 5 .. 10 {
+                tried += 1;
+                if names.contains_key(&id) {
+                    polonius_return!(names.get_mut(&id).unwrap());
+                }
+            }
+        });
+        names.entry(0).or_default()
+    };
+    name.push('!');
+    assert_eq!(tried, 3);
+    assert_eq!(names[&7], "seven!");
+}
@@ -0,0 +1,123 @@
+//! With the `nightly-try` feature, `?` works within the (emulated) bodies.
+
+use {
+    ::polonius_the_crab::prelude::*,
+    ::std::collections::HashMap,
+};
+
+#[derive(Debug, PartialEq)]
+struct Error(&'static str);
+
+impl From<::core::num::ParseIntError> for Error {
+    fn from (_: ::core::num::ParseIntError)
+      -> Self
+    {
+        Error("not a number")
+    }
+}
+
+fn get_or_parse<'map> (mut map: &'map mut HashMap<i32, String>, s: &str)
+  -> Result<&'map String, Error>
+{
+    let n = polonius!(|map| -> Result<&'polonius String, Error> {
+        // `From` conversion of the error.
+        let n: i32 = s.parse()?;
+        if let Some(value) = map.get(&n) {
+            polonius_return!(Ok(value));
+        }
+        exit_polonius!(n);
+    });
+    if n < 0 {
+        return Err(Error("negative"));
+    }
+    map.insert(n, n.to_string());
+    Ok(&map[&n])
+}
+
+fn first_key_with_value<'map> (mut map: &'map mut HashMap<i32, String>, keys: &[i32])
+  -> Option<&'map mut String>
+{
+    let mut keys = keys.iter();
+    polonius_loop!(|map| -> Option<&'polonius mut String> {
+        let key = keys.next()?;
+        if let Some(value) = map.get_mut(key) {
+            polonius_return!(Some(value));
+        }
+    })
+}
+
+#[test]
+fn question_mark_in_polonius ()
+{
+    let mut map = HashMap::new();
+    assert_eq!(get_or_parse(&mut map, "nope"), Err(Error("not a number")));
+    assert_eq!(get_or_parse(&mut map, "-1"), Err(Error("negative")));
+    assert_eq!(get_or_parse(&mut map, "42"), Ok(&"42".into()));
+    map.insert(42, "forty-two".into());
+    assert_eq!(get_or_parse(&mut map, "42"), Ok(&"forty-two".into()));
+}
+
+#[test]
+fn question_mark_in_polonius_loop ()
+{
+    let mut map = HashMap::from([(3, String::from("three"))]);
+    first_key_with_value(&mut map, &[1, 2, 3]).unwrap().push('!');
+    assert_eq!(map[&3], "three!");
+    assert_eq!(first_key_with_value(&mut map, &[1, 2]), None);
+}
+
+/// Like a genuine `?` (_e.g._, with the `polonius` feature), that of a labeled
+/// body `return`s from the function, rather than `break` out of the label.
+fn labeled_question_mark (mut map: &'_ mut HashMap<i32, String>, key: i32)
+  -> Result<&'static str, Error>
+{
+    let found: Result<&String, Error> = 'found: {
+        polonius!('found, |map| -> Result<&'polonius String, Error> {
+            let value = map.get(&key).ok_or(Error("missing"))?;
+            polonius_return!(Ok(value));
+        })
+    };
+    match found {
+        | Ok(_) => Ok("found"),
+        | Err(_) => Ok("broke out of 'found"),
+    }
+}
+
+fn labeled_question_mark_on_option (mut map: &'_ mut HashMap<i32, String>, key: i32)
+  -> Option<usize>
+{
+    let found: Option<&String> = 'found: {
+        polonius!('found, |map| -> Option<&'polonius String> {
+            polonius_return!(Some(map.get(&key)?));
+        })
+    };
+    Some(found.map_or(0, String::len))
+}
+
+/// No `?` involved: the function's return type need not absorb the residuals
+/// of the body's.
+fn labeled_option_in_infallible_fn (mut map: &'_ mut HashMap<i32, String>, key: i32)
+  -> usize
+{
+    let found: Option<&String> = 'found: {
+        polonius!('found, |map| -> Option<&'polonius String> {
+            if let Some(value) = map.get(&key) {
+                polonius_return!(Some(value));
+            }
+        });
+        None
+    };
+    found.map_or(0, String::len)
+}
+
+#[test]
+fn question_mark_in_labeled_polonius ()
+{
+    let mut map = HashMap::from([(0, "zero".to_owned())]);
+    assert_eq!(labeled_question_mark(&mut map, 0), Ok("found"));
+    assert_eq!(labeled_question_mark(&mut map, 1), Err(Error("missing")));
+    assert_eq!(labeled_question_mark_on_option(&mut map, 0), Some(4));
+    assert_eq!(labeled_question_mark_on_option(&mut map, 1), None);
+    assert_eq!(labeled_option_in_infallible_fn(&mut map, 0), 4);
+    assert_eq!(labeled_option_in_infallible_fn(&mut map, 1), 0);
+}
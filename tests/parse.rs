@@ -0,0 +1,157 @@
+#![forbid(unsafe_code)]
+
+use ::polonius_the_crab::{
+    ForLt,
+    parse::{try_alternatives, Backtrack},
+    polonius_alt,
+};
+
+/// A cursor which interns the parsed nodes in its own arena.
+struct Cursor<'src> {
+    rest: &'src [u8],
+    nodes: Vec<Node>,
+    attempts: usize,
+}
+
+#[derive(Debug, PartialEq)]
+enum Node {
+    Number(u32),
+    Ident(String),
+}
+
+impl<'src> Backtrack for Cursor<'src> {
+    type Checkpoint = (&'src [u8], usize);
+
+    fn checkpoint (&self)
+      -> Self::Checkpoint
+    {
+        (self.rest, self.nodes.len())
+    }
+
+    fn restore (&mut self, (rest, len): Self::Checkpoint)
+    {
+        self.rest = rest;
+        self.nodes.truncate(len);
+    }
+}
+
+impl Cursor<'_> {
+    fn new (src: &'_ str)
+      -> Cursor<'_>
+    {
+        Cursor { rest: src.as_bytes(), nodes: vec![], attempts: 0 }
+    }
+
+    fn push (&mut self, node: Node)
+      -> &mut Node
+    {
+        self.nodes.push(node);
+        self.nodes.last_mut().unwrap()
+    }
+
+    fn take_while (&mut self, pred: impl Fn(u8) -> bool)
+      -> &str
+    {
+        let len = self.rest.iter().position(|&b| !pred(b)).unwrap_or(self.rest.len());
+        let (taken, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        ::core::str::from_utf8(taken).unwrap()
+    }
+
+    /// Pushes a dummy node before failing, to check that it gets discarded.
+    fn number (&mut self)
+      -> Option<&mut Node>
+    {
+        self.attempts += 1;
+        self.push(Node::Number(0));
+        let n = self.take_while(|b| b.is_ascii_digit()).parse().ok()?;
+        if self.rest.first() == Some(&b'x') {
+            return None;
+        }
+        Some(self.push(Node::Number(n)))
+    }
+
+    fn ident (&mut self)
+      -> Option<&mut Node>
+    {
+        self.attempts += 1;
+        let ident = self.take_while(|b| b.is_ascii_alphanumeric()).to_owned();
+        if ident.is_empty() {
+            return None;
+        }
+        Some(self.push(Node::Ident(ident)))
+    }
+}
+
+fn atom<'c> (mut cursor: &'c mut Cursor<'_>)
+  -> Option<&'c mut Node>
+{
+    Some(polonius_alt!(|cursor| -> &'polonius mut Node {
+        cursor.number(),
+        cursor.ident(),
+    } else {
+        assert!(cursor.rest.is_empty() || !cursor.rest[0].is_ascii_alphanumeric());
+        return None;
+    }))
+}
+
+#[test]
+fn first_success_wins ()
+{
+    let mut cursor = Cursor::new("42 rest");
+    *atom(&mut cursor).unwrap() = Node::Number(43);
+    assert_eq!(cursor.nodes, [Node::Number(0), Node::Number(43)]);
+    assert_eq!(cursor.rest, b" rest");
+    assert_eq!(cursor.attempts, 1);
+}
+
+#[test]
+fn failed_alternatives_are_rolled_back ()
+{
+    let mut cursor = Cursor::new("12xy");
+    assert_eq!(atom(&mut cursor), Some(&mut Node::Ident("12xy".into())));
+    // The dummy `Number(0)` of the failed `number()` got discarded.
+    assert_eq!(cursor.nodes, [Node::Ident("12xy".into())]);
+    assert!(cursor.rest.is_empty());
+    assert_eq!(cursor.attempts, 2);
+}
+
+#[test]
+fn all_fail ()
+{
+    let mut cursor = Cursor::new("+1");
+    assert_eq!(atom(&mut cursor), None);
+    assert_eq!(cursor.rest, b"+1");
+    assert!(cursor.nodes.is_empty());
+    assert_eq!(cursor.attempts, 2);
+}
+
+#[test]
+fn try_alternatives_on_slices ()
+{
+    let mut tokens: &[&str] = &["let", "x"];
+    let res = try_alternatives::<_, ForLt!(<'r> = &'r &'static str)>(&mut tokens, &mut [
+        &mut |tokens| {
+            let (first, rest) = tokens.split_first()?;
+            *tokens = rest;
+            (*first == "fn").then_some(first)
+        },
+        &mut |tokens| {
+            let (first, rest) = tokens.split_first()?;
+            *tokens = rest;
+            (*first == "let").then_some(first)
+        },
+    ]);
+    assert_eq!(res.ok(), Some(&"let"));
+    assert_eq!(tokens, ["x"]);
+
+    let res = try_alternatives::<_, ForLt!(<'r> = &'r str)>(&mut tokens, &mut [
+        &mut |tokens| {
+            *tokens = &[];
+            None
+        },
+    ]);
+    assert!(res.is_err());
+    // Restored.
+    assert_eq!(tokens, ["x"]);
+}
@@ -0,0 +1,150 @@
+#![forbid(unsafe_code)]
+
+use {
+    ::polonius_the_crab::path::path,
+    ::std::{
+        cell::Cell,
+        collections::{BTreeMap, HashMap},
+    },
+};
+
+type Index = HashMap<String, HashMap<String, Vec<u32>>>;
+
+#[test]
+fn nested_maps ()
+{
+    let created = Cell::new(0);
+    let postings = |index: &mut Index, lang: &str, word: &str| {
+        let postings: &mut Vec<u32> =
+            path(index)
+                .step(
+                    |by_lang| by_lang.get_mut(lang),
+                    |by_lang| {
+                        created.set(created.get() + 1);
+                        by_lang.entry(lang.to_owned()).or_default()
+                    },
+                )
+                .step(
+                    |by_word| by_word.get_mut(word),
+                    |by_word| {
+                        created.set(created.get() + 1);
+                        by_word.entry(word.to_owned()).or_default()
+                    },
+                )
+                .into_mut()
+        ;
+        postings.push(postings.len() as u32);
+    };
+    let mut index = Index::new();
+    postings(&mut index, "en", "crab");
+    postings(&mut index, "en", "crab");
+    postings(&mut index, "en", "claw");
+    postings(&mut index, "fr", "crabe");
+    // 2 languages + 3 words.
+    assert_eq!(created.get(), 5);
+    assert_eq!(index["en"]["crab"], [0, 1]);
+    assert_eq!(index["en"]["claw"], [0]);
+    assert_eq!(index["fr"]["crabe"], [0]);
+}
+
+#[derive(Debug, PartialEq)]
+enum Value {
+    Null,
+    Number(f64),
+    Array(Vec<Value>),
+    Object(BTreeMap<String, Value>),
+}
+
+enum Key<'k> {
+    Field(&'k str),
+    Index(usize),
+}
+
+/// `/`-separated path, with numeric segments as array indices.
+fn keys (path: &'_ str)
+  -> impl '_ + Iterator<Item = Key<'_>>
+{
+    path.split('/').map(|segment| match segment.parse() {
+        | Ok(idx) => Key::Index(idx),
+        | Err(_) => Key::Field(segment),
+    })
+}
+
+fn lookup<'v> (value: &'v mut Value, key: &'_ Key<'_>)
+  -> Option<&'v mut Value>
+{
+    match (value, key) {
+        | (Value::Object(fields), &Key::Field(name)) => fields.get_mut(name),
+        | (Value::Array(items), &Key::Index(idx)) => items.get_mut(idx),
+        | _ => None,
+    }
+}
+
+/// Replaces non-container values, and pads arrays with `Null`s.
+fn create<'v> (value: &'v mut Value, key: Key<'_>)
+  -> &'v mut Value
+{
+    match key {
+        | Key::Field(name) => {
+            if !matches!(value, Value::Object(_)) {
+                *value = Value::Object(BTreeMap::new());
+            }
+            let Value::Object(fields) = value else { unreachable!() };
+            fields.entry(name.to_owned()).or_insert(Value::Null)
+        },
+        | Key::Index(idx) => {
+            if !matches!(value, Value::Array(_)) {
+                *value = Value::Array(vec![]);
+            }
+            let Value::Array(items) = value else { unreachable!() };
+            if items.len() <= idx {
+                items.resize_with(idx + 1, || Value::Null);
+            }
+            &mut items[idx]
+        },
+    }
+}
+
+fn at<'v> (root: &'v mut Value, p: &'_ str)
+  -> &'v mut Value
+{
+    path(root).descend(keys(p), lookup, create).into_mut()
+}
+
+#[test]
+fn json_like ()
+{
+    let mut root = Value::Null;
+    *at(&mut root, "servers/1/port") = Value::Number(8080.);
+    *at(&mut root, "servers/0/port") = Value::Number(80.);
+    if let Value::Number(port) = at(&mut root, "servers/1/port") {
+        *port += 1.;
+    }
+    let servers = at(&mut root, "servers");
+    let Value::Array(servers) = servers else { panic!("{servers:?}") };
+    assert_eq!(servers.len(), 2);
+    let port = |port| Value::Object(BTreeMap::from([("port".to_owned(), Value::Number(port))]));
+    assert_eq!(servers[..], [port(80.), port(8081.)]);
+
+    // A scalar in the way gets replaced.
+    *at(&mut root, "servers/0/port/number") = Value::Number(443.);
+    let Value::Object(fields) = at(&mut root, "servers/0/port") else { panic!() };
+    assert_eq!(fields["number"], Value::Number(443.));
+}
+
+#[test]
+fn existing_path_is_not_recreated ()
+{
+    let mut root = Value::Array(vec![Value::Object(BTreeMap::new())]);
+    fn no_create<'v> (_: &'v mut Value, _: Key<'_>)
+      -> &'v mut Value
+    {
+        unreachable!()
+    }
+    *path(&mut root).descend(keys("0"), lookup, no_create).into_mut() = Value::Null;
+    assert_eq!(root, Value::Array(vec![Value::Null]));
+    // no keys: the root itself.
+    let root_again = path(&mut root).descend(keys("").skip(1), lookup, no_create).into_mut();
+    *root_again = Value::Number(0.);
+    assert_eq!(root, Value::Number(0.));
+}
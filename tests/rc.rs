@@ -0,0 +1,67 @@
+#![forbid(unsafe_code)]
+
+use {
+    ::polonius_the_crab::rc::{get_mut_or_else, get_mut_or_try_else},
+    ::std::{
+        rc::Rc,
+        sync::Arc,
+    },
+};
+
+#[test]
+fn unique_is_borrowed_in_place ()
+{
+    let mut rc = Rc::new(vec![1, 2]);
+    let ptr = Rc::as_ptr(&rc);
+    get_mut_or_else(&mut rc, |_| unreachable!()).push(3);
+    assert_eq!(*rc, [1, 2, 3]);
+    assert_eq!(Rc::as_ptr(&rc), ptr);
+}
+
+#[test]
+fn shared_uses_the_fallback ()
+{
+    let mut rc = Rc::new(vec![1, 0, 2]);
+    let snapshot = Rc::clone(&rc);
+    // clone without the zeroes.
+    get_mut_or_else(&mut rc, |rc| {
+        *rc = Rc::new(rc.iter().copied().filter(|&x| x != 0).collect());
+        Rc::get_mut(rc).unwrap()
+    }).push(3);
+    assert_eq!(*rc, [1, 2, 3]);
+    assert_eq!(*snapshot, [1, 0, 2]);
+}
+
+#[test]
+fn unsized_pointee ()
+{
+    let mut rc: Rc<str> = "hello".into();
+    let _other = Rc::clone(&rc);
+    let s = get_mut_or_else(&mut rc, |rc| {
+        *rc = rc.to_uppercase().into();
+        Rc::get_mut(rc).unwrap()
+    });
+    s.make_ascii_lowercase();
+    assert_eq!(&*rc, "hello");
+}
+
+#[test]
+fn arc_try ()
+{
+    #[derive(Debug, PartialEq)]
+    struct Shared(usize);
+
+    let mut arc = Arc::new(String::from("a"));
+    get_mut_or_try_else(&mut arc, |arc| Err(Shared(Arc::strong_count(arc)))).unwrap().push('b');
+    let other = Arc::clone(&arc);
+    assert_eq!(
+        get_mut_or_try_else(&mut arc, |arc| Err(Shared(Arc::strong_count(arc)))),
+        Err(Shared(2)),
+    );
+    get_mut_or_try_else(&mut arc, |arc| {
+        *arc = Arc::new(format!("{arc}c"));
+        Arc::get_mut(arc)
+    }).unwrap().push('d');
+    assert_eq!(*arc, "abcd");
+    assert_eq!(*other, "ab");
+}
@@ -0,0 +1,93 @@
+#![forbid(unsafe_code)]
+
+use {
+    ::polonius_the_crab::sorted::{
+        get_or_insert_sorted,
+        get_or_insert_sorted_by_key,
+        get_or_try_insert_sorted,
+        get_or_try_insert_sorted_by_key,
+        upsert_sorted,
+    },
+    ::std::cell::Cell,
+};
+
+#[derive(Debug, PartialEq)]
+struct Entry {
+    name: String,
+    hits: u32,
+}
+
+fn entry (name: &'_ str)
+  -> Entry
+{
+    Entry { name: name.into(), hits: 0 }
+}
+
+#[test]
+fn by_key ()
+{
+    let mut entries = vec![];
+    for name in ["b", "a", "c", "a", "b", "a"] {
+        get_or_insert_sorted_by_key(&mut entries, name, |e: &Entry| &e.name, || entry(name))
+            .hits += 1
+        ;
+    }
+    let summary: Vec<_> = entries.iter().map(|e| (&*e.name, e.hits)).collect();
+    assert_eq!(summary, [("a", 3), ("b", 2), ("c", 1)]);
+}
+
+#[test]
+fn try_by_key ()
+{
+    let mut entries = vec![entry("m")];
+    let res = get_or_try_insert_sorted_by_key(&mut entries, "z", |e: &Entry| &e.name, || Err(()));
+    assert_eq!(res, Err(()));
+    assert_eq!(entries.len(), 1);
+    let res = get_or_try_insert_sorted_by_key(&mut entries, "a", |e: &Entry| &e.name, || Some(entry("a")));
+    res.unwrap().hits = 42;
+    assert_eq!(entries, [Entry { name: "a".into(), hits: 42 }, entry("m")]);
+}
+
+#[test]
+fn searches_once ()
+{
+    let comparisons = Cell::new(0);
+    let mut map: Vec<(u32, ())> = (0 .. 1024).map(|k| (2 * k, ())).collect();
+    get_or_insert_sorted_by_key(
+        &mut map,
+        &1001,
+        |(k, _)| {
+            comparisons.set(comparisons.get() + 1);
+            k
+        },
+        || (1001, ()),
+    );
+    // A single binary search over 1024 elements.
+    assert!(comparisons.get() <= 11, "{}", comparisons.get());
+    assert!(map.windows(2).all(|w| w[0].0 < w[1].0));
+    assert_eq!(map.len(), 1025);
+}
+
+#[test]
+fn pairs ()
+{
+    let mut map = vec![(1, "one".to_owned()), (3, "three".to_owned())];
+    get_or_insert_sorted(&mut map, 2, || "two".into()).push('!');
+    get_or_insert_sorted(&mut map, 3, || unreachable!()).push('!');
+    assert_eq!(map, [(1, "one".into()), (2, "two!".into()), (3, "three!".into())]);
+
+    assert_eq!(get_or_try_insert_sorted(&mut map, 0, || None), None);
+    let zero = get_or_try_insert_sorted(&mut map, 0, || Ok::<_, ()>("zero".into()));
+    assert_eq!(zero.unwrap(), "zero");
+    assert_eq!(map.len(), 4);
+}
+
+#[test]
+fn upsert ()
+{
+    let mut map = Vec::new();
+    for word in "to be or not to be".split(' ') {
+        upsert_sorted(&mut map, word, || 1, |n| *n += 1);
+    }
+    assert_eq!(map, [("be", 2), ("not", 1), ("or", 1), ("to", 2)]);
+}
@@ -7,7 +7,7 @@ fn cargo_check_with_polonius ()
         .env("RUSTC_BOOTSTRAP", "1")
         .args([
             "rustc", "--profile=check",
-            "--features", "polonius",
+            "--features", "polonius std",
             "--quiet",
             "--", "-Zpolonius", "-Funsafe_code",
         ])
@@ -18,3 +18,35 @@ fn cargo_check_with_polonius ()
         .then(|| panic!())
     ;
 }
+
+/// With the `polonius` feature, the macros expand to plain `return`s, `break`s
+/// and `continue`s: make sure `-Zpolonius` accepts these expansions as well,
+/// and that they behave the same (by running the other tests).
+#[test]
+fn cargo_test_macros_with_polonius ()
+{
+    if ::std::env::var_os("POLONIUS_THE_CRAB_NESTED_TEST").is_some() {
+        return;
+    }
+    let target_dir =
+        ::std::path::Path::new(env!("CARGO_TARGET_TMPDIR"))
+            .join("polonius")
+    ;
+    ::std::process::Command::new(env!("CARGO"))
+        .env("RUSTC_BOOTSTRAP", "1")
+        .env("RUSTFLAGS", "-Zpolonius")
+        .env("CARGO_TARGET_DIR", target_dir)
+        .env("POLONIUS_THE_CRAB_NESTED_TEST", "1")
+        .args([
+            "test",
+            "--features", "polonius std",
+            "--tests",
+            "--quiet",
+        ])
+        .status()
+        .unwrap()
+        .success()
+        .not()
+        .then(|| panic!())
+    ;
+}
@@ -0,0 +1,157 @@
+#![forbid(unsafe_code)]
+
+use {
+    ::core::{
+        future::Future,
+        pin::Pin,
+        task::{Context, Poll},
+    },
+    ::polonius_the_crab::{
+        prelude::*,
+        stream::{Item, LendingStream, LendingStreamItem},
+    },
+    ::std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        task::{Wake, Waker},
+    },
+};
+
+/// Counts its wake-ups.
+#[derive(Default)]
+struct CountingWaker(AtomicUsize);
+
+impl Wake for CountingWaker {
+    fn wake (self: Arc<Self>)
+    {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref (self: &'_ Arc<Self>)
+    {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// Decodes `;`-terminated frames into its internal buffer, which it lends.
+///
+/// Every other poll is `Pending`, as if waiting on the network.
+struct Frames {
+    incoming: &'static str,
+    frame: String,
+    pending: bool,
+}
+
+fn frames (incoming: &'static str)
+  -> Frames
+{
+    Frames { incoming, frame: String::new(), pending: false }
+}
+
+impl<'next> LendingStreamItem<'next> for Frames {
+    type T = &'next mut String;
+}
+
+impl LendingStream for Frames {
+    fn poll_next (&'_ mut self, cx: &'_ mut Context<'_>)
+      -> Poll<Option<&'_ mut String>>
+    {
+        self.pending = !self.pending;
+        if self.pending {
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        Poll::Ready(self.incoming.split_once(';').map(|(frame, rest)| {
+            self.incoming = rest;
+            self.frame.clear();
+            self.frame.push_str(frame);
+            &mut self.frame
+        }))
+    }
+}
+
+/// Busy-polls `stream` until its next item is ready: yet another
+/// conditional-return-of-a-borrow in a loop.
+fn block_on_next<'s, S : LendingStream> (
+    mut stream: &'s mut S,
+    waker: &'_ Arc<CountingWaker>,
+) -> Option<Item<'s, S>>
+{
+    let waker = Waker::from(waker.clone());
+    let cx = &mut Context::from_waker(&waker);
+    polonius_loop!(|stream| -> Option<Item<'polonius, S>> {
+        if let Poll::Ready(item) = stream.poll_next(cx) {
+            polonius_return!(item);
+        }
+    })
+}
+
+fn block_on<F : Future + Unpin> (
+    mut future: F,
+    waker: &'_ Arc<CountingWaker>,
+) -> F::Output
+{
+    let waker = Waker::from(waker.clone());
+    let cx = &mut Context::from_waker(&waker);
+    loop {
+        if let Poll::Ready(output) = Pin::new(&mut future).poll(cx) {
+            return output;
+        }
+    }
+}
+
+#[test]
+fn filter ()
+{
+    let waker = Arc::default();
+    let mut stream = frames("ping;data 1;ping;data 2;").filter(|frame| *frame != "ping");
+    while let Some(frame) = block_on_next(&mut stream, &waker) {
+        frame.push('!');
+        assert!(frame.starts_with("data"), "{frame}");
+    }
+    // 4 frames, plus the end of the stream, each after a `Pending` poll.
+    assert_eq!(waker.0.load(Ordering::SeqCst), 5);
+}
+
+#[test]
+fn take_while ()
+{
+    let waker = Arc::default();
+    let mut stream = frames("a;b;;c;").take_while(|frame| !frame.is_empty());
+    let mut seen = vec![];
+    while let Some(frame) = block_on_next(&mut stream, &waker) {
+        seen.push(frame.clone());
+    }
+    assert_eq!(seen, ["a", "b"]);
+    assert!(block_on_next(&mut stream, &waker).is_none());
+}
+
+#[test]
+fn find ()
+{
+    let waker = Arc::default();
+    let mut stream = frames("hello;ack 1;ack 2;");
+    let ack = block_on(stream.find(|frame| frame.starts_with("ack")), &waker).unwrap();
+    ack.push_str(" (seen)");
+    assert_eq!(ack, "ack 1 (seen)");
+    // the stream is usable again once the found item is no longer used.
+    assert_eq!(block_on_next(&mut stream, &waker).unwrap(), "ack 2");
+    assert!(block_on(stream.find(|_| true), &waker).is_none());
+}
+
+#[test]
+fn adapters_compose ()
+{
+    let waker = Arc::default();
+    let mut stream =
+        frames("1;x;2;3;stop;4;")
+            .filter(|frame| frame.parse::<u8>().is_ok() || *frame == "stop")
+            .take_while(|frame| *frame != "stop")
+    ;
+    let found = block_on(stream.find(|frame| *frame == "2"), &waker).unwrap();
+    found.push('2');
+    assert_eq!(block_on_next(&mut stream, &waker).unwrap(), "3");
+    assert!(block_on_next(&mut stream, &waker).is_none());
+}
@@ -0,0 +1,135 @@
+#![forbid(unsafe_code)]
+
+use ::polonius_the_crab::tree;
+
+#[derive(Debug)]
+struct Node {
+    name: &'static str,
+    value: i32,
+    children: Vec<Node>,
+}
+
+fn node (name: &'static str, value: i32, children: Vec<Node>)
+  -> Node
+{
+    Node { name, value, children }
+}
+
+///   root(0)
+///   ├── a(1)
+///   │   ├── a1(10)
+///   │   └── a2(20)
+///   │       └── a21(30)
+///   └── b(20)
+///       └── b1(30)
+fn sample ()
+  -> Node
+{
+    node("root", 0, vec![
+        node("a", 1, vec![
+            node("a1", 10, vec![]),
+            node("a2", 20, vec![
+                node("a21", 30, vec![]),
+            ]),
+        ]),
+        node("b", 20, vec![
+            node("b1", 30, vec![]),
+        ]),
+    ])
+}
+
+fn children (node: &'_ mut Node)
+  -> &'_ mut [Node]
+{
+    &mut node.children
+}
+
+#[test]
+fn dfs_is_pre_order ()
+{
+    let mut root = sample();
+    let found = tree::find_mut_dfs(&mut root, children, |n| n.value == 20).unwrap();
+    assert_eq!(found.name, "a2");
+    found.value = -1;
+    assert_eq!(root.children[0].children[1].value, -1);
+
+    let found = tree::find_mut_dfs(&mut root, children, |n| n.value == 30).unwrap();
+    assert_eq!(found.name, "a21");
+}
+
+#[test]
+fn bfs_is_level_order ()
+{
+    let mut root = sample();
+    let found = tree::find_mut_bfs(&mut root, children, |n| n.value == 20).unwrap();
+    assert_eq!(found.name, "b");
+    found.children.clear();
+    assert!(root.children[1].children.is_empty());
+
+    let found = tree::find_mut_bfs(&mut root, children, |n| n.value == 30).unwrap();
+    assert_eq!(found.name, "a21");
+}
+
+#[test]
+fn root_and_not_found ()
+{
+    let mut root = sample();
+    assert_eq!(tree::find_mut_dfs(&mut root, children, |_| true).unwrap().name, "root");
+    assert_eq!(tree::find_mut_bfs(&mut root, children, |_| true).unwrap().name, "root");
+    assert!(tree::find_mut_dfs(&mut root, children, |n| n.value == 42).is_none());
+    assert!(tree::find_mut_bfs(&mut root, children, |n| n.value == 42).is_none());
+}
+
+#[test]
+fn find_map_borrows_part_of_the_node ()
+{
+    let mut root = sample();
+    let mut visited = vec![];
+    let value = tree::find_map_mut_dfs(&mut root, children, |n| {
+        visited.push(n.name);
+        Some(&mut n.value).filter(|v| **v >= 20)
+    }).unwrap();
+    *value += 1;
+    assert_eq!(visited, ["root", "a", "a1", "a2"]);
+    assert_eq!(root.children[0].children[1].value, 21);
+
+    let name = tree::find_map_mut_bfs(&mut root, children, |n| {
+        (n.value == 30).then_some(&mut n.name)
+    }).unwrap();
+    *name = "renamed";
+    // `b1` is shallower than `a21`.
+    assert_eq!(root.children[1].children[0].name, "renamed");
+}
+
+/// A JSON-like recursive `enum`, whose leaves have no children.
+#[derive(Debug, PartialEq)]
+enum Value {
+    Number(i64),
+    Array(Vec<Value>),
+}
+
+#[test]
+fn enum_tree ()
+{
+    let mut value = Value::Array(vec![
+        Value::Number(1),
+        Value::Array(vec![Value::Number(2), Value::Number(3)]),
+    ]);
+    fn children (v: &'_ mut Value)
+      -> &'_ mut [Value]
+    {
+        match v {
+            | Value::Array(items) => items,
+            | Value::Number(_) => &mut [],
+        }
+    }
+    let n = tree::find_map_mut_dfs(&mut value, children, |v| match v {
+        | Value::Number(n) if *n % 2 == 0 => Some(n),
+        | _ => None,
+    }).unwrap();
+    *n *= 10;
+    assert_eq!(value, Value::Array(vec![
+        Value::Number(1),
+        Value::Array(vec![Value::Number(20), Value::Number(3)]),
+    ]));
+}